@@ -3,17 +3,19 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
+pub mod punycode;
+
 
 
 #[elrond_wasm::module]
 pub trait Utils {
 
     fn to_wei(&self, amount: BigUint) -> BigUint {            
-        amount * &BigUint::from(10 as u32).pow(18)
+        amount * &BigUint::from(10_u32).pow(18)
     }
 
     fn to_egld(&self, amount: BigUint) -> BigUint {
-        amount / &BigUint::from(10 as u32).pow(18)
+        amount / &BigUint::from(10_u32).pow(18)
     }
 
 }
\ No newline at end of file
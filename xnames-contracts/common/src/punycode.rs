@@ -0,0 +1,83 @@
+/// Prefix of the labels encoding unicode characters with punycode.
+pub const PUNYCODE_PREFIX: &[u8] = b"xn--";
+
+// Punycode parameters, as defined in RFC 3492
+const PUNYCODE_BASE: u64 = 36;
+const PUNYCODE_TMIN: u64 = 1;
+const PUNYCODE_TMAX: u64 = 26;
+const PUNYCODE_SKEW: u64 = 38;
+const PUNYCODE_DAMP: u64 = 700;
+const PUNYCODE_INITIAL_BIAS: u64 = 72;
+
+/// Returns the number of characters a punycode string decodes to, following
+/// the decoding of RFC 3492 without building the decoded string, or `None`
+/// if the string is not valid punycode.
+pub fn punycode_length(encoded: &[u8]) -> Option<usize> {
+    // Basic characters are copied before the last delimiter
+    let (mut length, deltas) = match encoded.iter().rposition(|byte| *byte == b'-') {
+        Some(delimiter) => (delimiter, &encoded[delimiter + 1..]),
+        None => (0, encoded),
+    };
+
+    let mut bias = PUNYCODE_INITIAL_BIAS;
+    let mut i: u64 = 0;
+    let mut position = 0;
+    while position < deltas.len() {
+        let old_i = i;
+        let mut weight: u64 = 1;
+        let mut k = PUNYCODE_BASE;
+        loop {
+            let digit = punycode_digit(*deltas.get(position)?)?;
+            position += 1;
+            i = i.checked_add(digit.checked_mul(weight)?)?;
+
+            let threshold = if k <= bias {
+                PUNYCODE_TMIN
+            } else if k >= bias + PUNYCODE_TMAX {
+                PUNYCODE_TMAX
+            } else {
+                k - bias
+            };
+            if digit < threshold {
+                break;
+            }
+            weight = weight.checked_mul(PUNYCODE_BASE - threshold)?;
+            k += PUNYCODE_BASE;
+        }
+
+        length += 1;
+        bias = punycode_adapt(i - old_i, length as u64, old_i == 0);
+        i = i % length as u64 + 1;
+    }
+
+    if length == 0 {
+        None
+    } else {
+        Some(length)
+    }
+}
+
+fn punycode_digit(byte: u8) -> Option<u64> {
+    match byte {
+        b'a'..=b'z' => Some((byte - b'a') as u64),
+        b'A'..=b'Z' => Some((byte - b'A') as u64),
+        b'0'..=b'9' => Some((byte - b'0') as u64 + 26),
+        _ => None,
+    }
+}
+
+fn punycode_adapt(delta: u64, num_points: u64, first_time: bool) -> u64 {
+    let mut delta = if first_time {
+        delta / PUNYCODE_DAMP
+    } else {
+        delta / 2
+    };
+    delta += delta / num_points;
+
+    let mut k = 0;
+    while delta > ((PUNYCODE_BASE - PUNYCODE_TMIN) * PUNYCODE_TMAX) / 2 {
+        delta /= PUNYCODE_BASE - PUNYCODE_TMIN;
+        k += PUNYCODE_BASE;
+    }
+    k + ((PUNYCODE_BASE - PUNYCODE_TMIN + 1) * delta) / (delta + PUNYCODE_SKEW)
+}
@@ -0,0 +1,47 @@
+#![no_std]
+
+elrond_wasm::imports!();
+
+/// Mock of a smart contract receiving domains from `transfer`, accepting or
+/// rejecting them as set on deploy. The last domain received is recorded,
+/// along with its sender and the transfer data.
+#[elrond_wasm::contract]
+pub trait DomainReceiverMock {
+    #[init]
+    fn init(&self, accept: bool) {
+        self.accept().set(accept);
+    }
+
+    #[payable("*")]
+    #[endpoint(onDomainReceived)]
+    fn on_domain_received(
+        &self,
+        domain: ManagedBuffer,
+        from: ManagedAddress,
+        data: ManagedBuffer,
+    ) -> bool {
+        self.last_domain().set(&domain);
+        self.last_from().set(&from);
+        self.last_data().set(&data);
+
+        self.accept().get()
+    }
+
+    // storage
+
+    #[view(getAccept)]
+    #[storage_mapper("accept")]
+    fn accept(&self) -> SingleValueMapper<bool>;
+
+    #[view(getLastDomain)]
+    #[storage_mapper("lastDomain")]
+    fn last_domain(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getLastFrom)]
+    #[storage_mapper("lastFrom")]
+    fn last_from(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getLastData)]
+    #[storage_mapper("lastData")]
+    fn last_data(&self) -> SingleValueMapper<ManagedBuffer>;
+}
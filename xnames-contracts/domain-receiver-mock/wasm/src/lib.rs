@@ -0,0 +1,25 @@
+// Code generated by the elrond-wasm multi-contract system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Endpoints:                            5
+// Async Callback (empty):               1
+// Total number of exported functions:   7
+
+#![no_std]
+
+elrond_wasm_node::wasm_endpoints! {
+    domain_receiver_mock
+    (
+        onDomainReceived
+        getAccept
+        getLastDomain
+        getLastFrom
+        getLastData
+    )
+}
+
+elrond_wasm_node::wasm_empty_callback! {}
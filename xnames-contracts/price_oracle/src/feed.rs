@@ -0,0 +1,220 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use elrond_wasm::types::heap::Vec;
+
+const DEVIATION_DENOMINATOR: u64 = 10_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct RateSubmission<M: ManagedTypeApi> {
+    pub rate: BigUint<M>,
+    pub timestamp: u64,
+}
+
+/// EGLD/USD rate aggregated from the rates submitted by the authorized
+/// feeders, used to convert the prices set in USD cents to EGLD. The rate is
+/// the median of the fresh submissions, once there are at least `quorum` of
+/// them.
+#[elrond_wasm::module]
+pub trait Feed {
+    /// Authorizes `feeder` to push the EGLD/USD rate.
+    #[only_owner]
+    #[endpoint]
+    fn add_feeder(&self, feeder: ManagedAddress) {
+        self.feeders().insert(feeder);
+    }
+
+    /// Revokes the authorization of `feeder` to push the EGLD/USD rate.
+    #[only_owner]
+    #[endpoint]
+    fn remove_feeder(&self, feeder: ManagedAddress) {
+        require!(self.feeders().swap_remove(&feeder), "Unknown feeder!");
+        self.submissions().remove(&feeder);
+    }
+
+    /// Sets how many fresh submissions are needed to update the EGLD/USD rate.
+    #[only_owner]
+    #[endpoint]
+    fn set_quorum(&self, quorum: usize) {
+        require!(quorum > 0, "Quorum should be a positive number");
+        self.quorum().set(quorum);
+    }
+
+    /// Sets by how much a submission can deviate from the current EGLD/USD
+    /// rate, between 0 (0%) and 10_000 (100%). Zero disables the check.
+    #[only_owner]
+    #[endpoint]
+    fn set_max_deviation(&self, max_deviation: u64) {
+        require!(
+            max_deviation <= DEVIATION_DENOMINATOR,
+            "Max deviation should not exceed 100%"
+        );
+        self.max_deviation().set(max_deviation);
+    }
+
+    /// Sets for how many seconds a pushed EGLD/USD rate can be used.
+    #[only_owner]
+    #[endpoint]
+    fn set_max_rate_age(&self, max_rate_age: u64) {
+        require!(max_rate_age > 0, "Max rate age should be a positive number");
+        self.max_rate_age().set(max_rate_age);
+    }
+
+    /// Submits the price of one EGLD in USD cents, and updates the EGLD/USD
+    /// rate if there are enough fresh submissions. Submissions are rejected
+    /// until both the quorum and the max rate age are set.
+    #[endpoint]
+    fn push_usd_rate(&self, rate: BigUint) {
+        let caller = self.blockchain().get_caller();
+        require!(self.feeders().contains(&caller), "Unauthorized feeder!");
+        require!(
+            !self.quorum().is_empty() && !self.max_rate_age().is_empty(),
+            "Quorum and max rate age are not set!"
+        );
+        require!(rate > 0, "Rate should be a positive number");
+        self.require_within_deviation(&rate);
+
+        let timestamp = self.blockchain().get_block_timestamp();
+        self.submissions().insert(
+            caller.clone(),
+            RateSubmission {
+                rate: rate.clone(),
+                timestamp,
+            },
+        );
+
+        self.push_usd_rate_event(&caller, &rate, timestamp);
+
+        if let Some(median) = self.median_fresh_submission(timestamp) {
+            self.usd_rate().set(&median);
+            self.usd_rate_updated_at().set(timestamp);
+
+            self.update_usd_rate_event(&median, timestamp);
+        }
+    }
+
+    /// Returns the price of one EGLD in USD cents and when it was updated.
+    #[view]
+    fn get_usd_rate(&self) -> MultiValue2<BigUint, u64> {
+        (self.usd_rate().get(), self.usd_rate_updated_at().get()).into()
+    }
+
+    /// Returns the feeders authorized to push the EGLD/USD rate.
+    #[view]
+    fn get_feeders(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.feeders().iter().collect()
+    }
+
+    /// Returns the latest submission of each feeder.
+    #[view]
+    fn get_submissions(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress, RateSubmission<Self::Api>>> {
+        let mut result = MultiValueEncoded::new();
+
+        for (feeder, submission) in self.submissions().iter() {
+            result.push((feeder, submission).into());
+        }
+
+        result
+    }
+
+    /// Rejects a rate deviating from the current EGLD/USD rate by more than
+    /// the max deviation. Any rate is accepted while the current one is stale.
+    fn require_within_deviation(&self, rate: &BigUint) {
+        let max_deviation = self.max_deviation().get();
+        let current_rate = self.usd_rate().get();
+        if max_deviation == 0 || current_rate == 0 {
+            return;
+        }
+
+        let age = self
+            .blockchain()
+            .get_block_timestamp()
+            .saturating_sub(self.usd_rate_updated_at().get());
+        if age > self.max_rate_age().get() {
+            return;
+        }
+
+        let deviation = if rate > &current_rate {
+            rate - &current_rate
+        } else {
+            &current_rate - rate
+        };
+        require!(
+            deviation * DEVIATION_DENOMINATOR <= current_rate * max_deviation,
+            "Rate deviates too much from the current rate!"
+        );
+    }
+
+    /// Returns the median of the fresh submissions, if there are at least
+    /// `quorum` of them.
+    fn median_fresh_submission(&self, timestamp: u64) -> Option<BigUint> {
+        let max_rate_age = self.max_rate_age().get();
+        let mut rates: Vec<BigUint> = self
+            .submissions()
+            .values()
+            .filter(|submission| timestamp.saturating_sub(submission.timestamp) <= max_rate_age)
+            .map(|submission| submission.rate)
+            .collect();
+
+        if rates.is_empty() || rates.len() < self.quorum().get() {
+            return None;
+        }
+
+        rates.sort();
+        let middle = rates.len() / 2;
+        if rates.len() % 2 == 0 {
+            Some((&rates[middle - 1] + &rates[middle]) / 2_u32)
+        } else {
+            Some(rates.swap_remove(middle))
+        }
+    }
+
+    /// Returns the EGLD/USD rate, rejecting if it was never pushed or is older
+    /// than the max rate age.
+    fn fresh_usd_rate(&self) -> BigUint {
+        let rate = self.usd_rate().get();
+        require!(rate > 0, "EGLD/USD rate is not set!");
+
+        let age = self
+            .blockchain()
+            .get_block_timestamp()
+            .saturating_sub(self.usd_rate_updated_at().get());
+        require!(age <= self.max_rate_age().get(), "EGLD/USD rate is stale!");
+
+        rate
+    }
+
+    #[storage_mapper("feeders")]
+    fn feeders(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("submissions")]
+    fn submissions(&self) -> MapMapper<ManagedAddress, RateSubmission<Self::Api>>;
+
+    #[storage_mapper("quorum")]
+    fn quorum(&self) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("max_deviation")]
+    fn max_deviation(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("max_rate_age")]
+    fn max_rate_age(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("usd_rate")]
+    fn usd_rate(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("usd_rate_updated_at")]
+    fn usd_rate_updated_at(&self) -> SingleValueMapper<u64>;
+
+    #[event("push_usd_rate")]
+    fn push_usd_rate_event(
+        &self,
+        #[indexed] feeder: &ManagedAddress,
+        #[indexed] rate: &BigUint,
+        timestamp: u64,
+    );
+
+    #[event("update_usd_rate")]
+    fn update_usd_rate_event(&self, #[indexed] rate: &BigUint, timestamp: u64);
+}
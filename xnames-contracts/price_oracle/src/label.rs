@@ -0,0 +1,139 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common::punycode::{punycode_length, PUNYCODE_PREFIX};
+
+const MULTIPLIER_DENOMINATOR: u32 = 10_000;
+
+/// Characters a label is made of. Punycode (`xn--`) labels encode unicode
+/// characters such as emoji, so they are non-ASCII.
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Eq, Clone, Copy, Debug,
+)]
+pub enum CharacterClass {
+    Digits,
+    Letters,
+    Mixed,
+    NonAscii,
+}
+
+impl CharacterClass {
+    pub fn of(label: &[u8]) -> Self {
+        if label.starts_with(PUNYCODE_PREFIX) || !label.is_ascii() {
+            CharacterClass::NonAscii
+        } else if label.iter().all(u8::is_ascii_digit) {
+            CharacterClass::Digits
+        } else if label.iter().all(u8::is_ascii_alphabetic) {
+            CharacterClass::Letters
+        } else {
+            CharacterClass::Mixed
+        }
+    }
+}
+
+/// Returns the length of a label in characters, counting the bytes that do
+/// not continue a UTF-8 character. Punycode (`xn--`) labels are counted by
+/// their decoded characters.
+pub fn label_length(label: &[u8]) -> usize {
+    if let Some(encoded) = label.strip_prefix(PUNYCODE_PREFIX) {
+        if let Some(length) = punycode_length(encoded) {
+            return length;
+        }
+    }
+
+    label.iter().filter(|byte| *byte & 0xC0 != 0x80).count()
+}
+
+fn lowercase<M: ManagedTypeApi>(label: &ManagedBuffer<M>) -> ManagedBuffer<M> {
+    let mut bytes = label.to_boxed_bytes();
+    bytes.as_mut_slice().make_ascii_lowercase();
+    ManagedBuffer::new_from_bytes(bytes.as_slice())
+}
+
+/// Prices depending on the label itself: multipliers by character class on
+/// top of the length based price, and individually priced premium labels.
+#[elrond_wasm::module]
+pub trait Label {
+    /// Sets the multiplier of the price of the labels of a character class,
+    /// where 10_000 keeps the length based price.
+    #[only_owner]
+    #[endpoint]
+    fn set_class_multiplier(&self, class: CharacterClass, multiplier: u32) {
+        require!(multiplier > 0, "Multiplier should be a positive number");
+        self.class_multipliers().insert(class, multiplier);
+    }
+
+    /// Prices `label` at `price` a year regardless of its length and
+    /// character class, in the currency of the prices. The label is
+    /// lowercased, like the domains priced by the registry.
+    #[only_owner]
+    #[endpoint]
+    fn set_premium_label(&self, label: ManagedBuffer, price: BigUint) {
+        require!(!label.is_empty(), "Label is empty!");
+        require!(price > 0, "Price should be a positive number");
+        self.premium_labels().insert(lowercase(&label), price);
+    }
+
+    /// Prices `label` by its length and character class again.
+    #[only_owner]
+    #[endpoint]
+    fn remove_premium_label(&self, label: ManagedBuffer) {
+        require!(
+            self.premium_labels().remove(&lowercase(&label)).is_some(),
+            "Label is not a premium label!"
+        );
+    }
+
+    /// Returns the character class of a label.
+    #[view]
+    fn get_character_class(&self, label: ManagedBuffer) -> CharacterClass {
+        CharacterClass::of(label.to_boxed_bytes().as_slice())
+    }
+
+    /// Returns the multiplier of every character class.
+    #[view]
+    fn get_class_multipliers(&self) -> MultiValueEncoded<MultiValue2<CharacterClass, u32>> {
+        let mut result = MultiValueEncoded::new();
+
+        for class in [
+            CharacterClass::Digits,
+            CharacterClass::Letters,
+            CharacterClass::Mixed,
+            CharacterClass::NonAscii,
+        ] {
+            result.push((class, self.class_multiplier(class)).into());
+        }
+
+        result
+    }
+
+    /// Returns the premium labels and their yearly prices.
+    #[view]
+    fn get_premium_labels(&self) -> MultiValueEncoded<MultiValue2<ManagedBuffer, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+
+        for (label, price) in self.premium_labels().iter() {
+            result.push((label, price).into());
+        }
+
+        result
+    }
+
+    fn class_multiplier(&self, class: CharacterClass) -> u32 {
+        self.class_multipliers()
+            .get(&class)
+            .unwrap_or(MULTIPLIER_DENOMINATOR)
+    }
+
+    /// Applies the multiplier of the character class of a label to its
+    /// length based price.
+    fn apply_class_multiplier(&self, class: CharacterClass, price: BigUint) -> BigUint {
+        price * self.class_multiplier(class) / MULTIPLIER_DENOMINATOR
+    }
+
+    #[storage_mapper("class_multipliers")]
+    fn class_multipliers(&self) -> MapMapper<CharacterClass, u32>;
+
+    #[storage_mapper("premium_labels")]
+    fn premium_labels(&self) -> MapMapper<ManagedBuffer, BigUint>;
+}
@@ -3,110 +3,380 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
+mod feed;
+mod label;
+
+use label::{label_length, CharacterClass};
+
+const SECONDS_IN_YEAR: u64 = 31_536_000;
+const DISCOUNT_DENOMINATOR: u32 = 10_000;
+
 #[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Eq, Clone, Debug)]
 pub enum PriceType {
     Fixed,
     Dynamic,
 }
 
+/// Currency the prices are set in. Prices in USD are set in cents and
+/// converted to EGLD with the EGLD/USD rate of the feeders.
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Eq, Clone, Debug)]
+pub enum PriceCurrency {
+    Egld,
+    Usd,
+}
+
 #[derive(
     TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, ManagedVecItem,
 )]
-pub struct PriceItem<M: ManagedTypeApi> {
+pub struct PriceTier<M: ManagedTypeApi> {
+    min_length: u8,
+    max_length: u8,
     price: BigUint<M>,
-    length: u8,
+}
+
+/// Discount on subscriptions of at least `years` years, between 0 (0%) and
+/// 10_000 (100%).
+#[derive(
+    TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, ManagedVecItem,
+)]
+pub struct DiscountItem {
+    years: u64,
+    discount: u32,
 }
 
 #[elrond_wasm::contract]
-pub trait PriceOracleContract: common::Utils {
+pub trait PriceOracleContract: common::Utils + feed::Feed + label::Label {
     #[init]
     fn init(&self) {
         self.current_type().set(&PriceType::Fixed);
+        self.current_currency().set(&PriceCurrency::Egld);
         self.price_fixed().set(&BigUint::zero());
     }
 
+    /// Returns the yearly price of a domain label, in `token`. Prices are set
+    /// in EGLD or USD cents and converted with the rate of the token.
+    #[view]
+    fn get_price(&self, label: ManagedBuffer, token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        let price = self.egld_price(&label);
+        self.convert_egld(price, token)
+    }
+
+    /// Returns the full tier table, i.e. the price of every length range,
+    /// including the ranges priced by the less and more prices.
+    #[view]
+    fn get_price_tiers(&self) -> MultiValueEncoded<PriceTier<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+
+        match self.current_type().get() {
+            PriceType::Fixed => result.push(PriceTier {
+                min_length: 1,
+                max_length: u8::MAX,
+                price: self.price_fixed().get(),
+            }),
+            PriceType::Dynamic => {
+                let first_tier = self.price_tiers().get(1);
+                if first_tier.min_length > 1 {
+                    result.push(PriceTier {
+                        min_length: 1,
+                        max_length: first_tier.min_length - 1,
+                        price: self.price_less().get(),
+                    });
+                }
+
+                let mut last_max_length = 0;
+                for tier in self.price_tiers().iter() {
+                    last_max_length = tier.max_length;
+                    result.push(tier);
+                }
+
+                if last_max_length < u8::MAX {
+                    result.push(PriceTier {
+                        min_length: last_max_length + 1,
+                        max_length: u8::MAX,
+                        price: self.price_more().get(),
+                    });
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Returns the price of a domain label for `duration`
+    /// seconds, in `token`. The yearly price is prorated over the duration,
+    /// and discounted by the discount tier of the longest number of whole
+    /// years the duration covers.
+    #[view]
+    fn get_price_for_duration(
+        &self,
+        label: ManagedBuffer,
+        duration: u64,
+        token: EgldOrEsdtTokenIdentifier,
+    ) -> BigUint {
+        require!(duration > 0, "Duration must not be zero!");
+        require!(
+            duration >= self.min_duration().get(),
+            "Duration is less than the minimum duration!"
+        );
+
+        let price = self.egld_price(&label) * duration / SECONDS_IN_YEAR;
+        let discount = self
+            .discounts()
+            .iter()
+            .filter(|item| duration / SECONDS_IN_YEAR >= item.years)
+            .last()
+            .map_or(0, |item| item.discount);
+        let price = price * (DISCOUNT_DENOMINATOR - discount) / DISCOUNT_DENOMINATOR;
+
+        self.convert_egld(price, token)
+    }
+
+    /// Returns the discount tiers.
+    #[view]
+    fn get_discounts(&self) -> MultiValueEncoded<DiscountItem> {
+        self.discounts().iter().collect()
+    }
+
+    /// Returns the minimum subscription duration in seconds.
+    #[view]
+    fn get_min_duration(&self) -> u64 {
+        self.min_duration().get()
+    }
+
+    /// Converts an amount of EGLD to `token` with the rate of the token.
+    #[view]
+    fn convert_egld(&self, amount: BigUint, token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        if token.is_egld() {
+            return amount;
+        }
+
+        let rate = self
+            .token_rates()
+            .get(&token)
+            .unwrap_or_else(|| sc_panic!("Token is not accepted!"));
+        amount * &rate / &self.to_wei(BigUint::from(1_u32))
+    }
+
+    /// Returns the accepted tokens and their rates, i.e. the amount of the
+    /// token, in its smallest unit, worth one EGLD.
+    #[view]
+    fn get_token_rates(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<EgldOrEsdtTokenIdentifier, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+
+        for (token, rate) in self.token_rates().iter() {
+            result.push((token, rate).into());
+        }
+
+        result
+    }
+
+    /// Returns the yearly price of a domain label in USD cents and in EGLD.
     #[view]
-    fn get_price(&self, length: u8) -> BigUint {
+    fn get_usd_and_egld_price(&self, label: ManagedBuffer) -> MultiValue2<BigUint, BigUint> {
+        let price = self.label_price(&label);
+        let wei = self.to_wei(BigUint::from(1_u32));
+
+        match self.current_currency().get() {
+            PriceCurrency::Egld => {
+                let usd_price = &price * &self.fresh_usd_rate() / &wei;
+                (usd_price, price).into()
+            }
+            PriceCurrency::Usd => {
+                let egld_price = &price * &wei / &self.fresh_usd_rate();
+                (price, egld_price).into()
+            }
+        }
+    }
+
+    fn egld_price(&self, label: &ManagedBuffer) -> BigUint {
+        let price = self.label_price(label);
+        match self.current_currency().get() {
+            PriceCurrency::Egld => price,
+            PriceCurrency::Usd => {
+                price * &self.to_wei(BigUint::from(1_u32)) / &self.fresh_usd_rate()
+            }
+        }
+    }
+
+    /// Returns the yearly price of a label in the currency of the prices: its
+    /// premium price if it is a premium label, otherwise the price of its
+    /// length in characters with the multiplier of its character class.
+    fn label_price(&self, label: &ManagedBuffer) -> BigUint {
+        if let Some(price) = self.premium_labels().get(label) {
+            return price;
+        }
+
+        let bytes = label.to_boxed_bytes();
+        let length = label_length(bytes.as_slice()).min(u8::MAX as usize) as u8;
+        self.apply_class_multiplier(
+            CharacterClass::of(bytes.as_slice()),
+            self.tier_price(length),
+        )
+    }
+
+    fn tier_price(&self, length: u8) -> BigUint {
         let price_type = self.current_type().get();
         match price_type {
             PriceType::Fixed => self.price_fixed().get(),
             PriceType::Dynamic => {
-                let first_item = self.price_mid().get(1);
-                require!(
-                    first_item.price > 0,
-                    "First element less than zero"
-                );
-                let last_item = self.price_mid().get(self.price_mid().len());
-                if first_item.length > length {
+                // Tiers are sorted and contiguous, so the first tier ending at
+                // or after the length holds it
+                if length < self.price_tiers().get(1).min_length {
                     return self.price_less().get();
                 }
-                if last_item.length < length {
-                    return self.price_more().get();
-                }
-                let found = self
-                    .price_mid()
+                self.price_tiers()
                     .iter()
-                    .find(|item| item.length == length);
-                if found.is_none() {
-                    return self.price_more().get();
-                } else {
-                    return found.expect("unwrap found price").price;
-                }
+                    .find(|tier| length <= tier.max_length)
+                    .map_or_else(|| self.price_more().get(), |tier| tier.price)
             }
-            _ => BigUint::zero(),
         }
     }
 
-    
-
+    /// Sets the prices. `Fixed` prices every length at `price`. `Dynamic`
+    /// prices lengths below the first tier at `price`, lengths in a tier at
+    /// the price of the tier and lengths above the last tier at `price_more`.
+    /// Tiers should be sorted and contiguous length ranges.
+    #[only_owner]
     #[endpoint]
     fn set_price(
         &self,
         price_type: PriceType,
         price: BigUint,
-        price_mid: ManagedVec<PriceItem<Self::Api>>,
+        price_tiers: ManagedVec<PriceTier<Self::Api>>,
         price_more: BigUint,
     ) {
         match price_type {
             PriceType::Fixed => {
                 self.current_type().set(price_type);
-                self.price_fixed().set(self.to_wei(price));
+                match self.current_currency().get() {
+                    PriceCurrency::Egld => self.price_fixed().set(self.to_wei(price)),
+                    PriceCurrency::Usd => self.price_fixed().set(price),
+                }
             }
             PriceType::Dynamic => {
                 self.current_type().set(price_type);
                 require!(price > 0, "Less price should be a positive number");
                 self.price_less().set(price);
-                require!(
-                    !price_mid.is_empty(),
-                    "Price Mid is empty!"
-                );
-                self.price_mid().clear();
-                price_mid.iter().for_each(|item| {
-                    let item = PriceItem {
-                        length: item.length,
-                        price: item.price.clone(),
-                    };
-                    
-                    self.price_mid().push(&item);
-                });
+                require!(!price_tiers.is_empty(), "Price tiers are empty!");
+
+                let mut previous_max_length: Option<u8> = None;
+                for tier in price_tiers.iter() {
+                    require!(tier.min_length > 0, "Tier lengths should start from 1");
+                    require!(
+                        tier.min_length <= tier.max_length,
+                        "Tier min length is more than its max length"
+                    );
+                    if let Some(previous_max_length) = previous_max_length {
+                        require!(
+                            tier.min_length > previous_max_length,
+                            "Tiers are overlapping or unsorted"
+                        );
+                        require!(
+                            tier.min_length == previous_max_length + 1,
+                            "Tiers leave a gap between lengths"
+                        );
+                    }
+                    require!(tier.price > 0, "Tier price should be a positive number");
+                    previous_max_length = Some(tier.max_length);
+                }
+
+                self.price_tiers().clear();
+                for tier in price_tiers.iter() {
+                    self.price_tiers().push(&tier);
+                }
 
+                require!(price_more > 0, "More price should be a positive number");
                 self.price_more().set(price_more);
             }
         }
     }
 
+    /// Sets the discount tiers, sorted by strictly increasing number of years.
+    #[only_owner]
+    #[endpoint]
+    fn set_discounts(&self, discounts: ManagedVec<DiscountItem>) {
+        let mut previous_years = 0;
+        for item in discounts.iter() {
+            require!(
+                item.years > previous_years,
+                "Discounts should be sorted by increasing years"
+            );
+            require!(
+                item.discount <= DISCOUNT_DENOMINATOR,
+                "Discount should not exceed 100%"
+            );
+            previous_years = item.years;
+        }
+
+        self.discounts().clear();
+        for item in discounts.iter() {
+            self.discounts().push(&item);
+        }
+    }
+
+    /// Sets the minimum subscription duration in seconds.
+    #[only_owner]
+    #[endpoint]
+    fn set_min_duration(&self, min_duration: u64) {
+        self.min_duration().set(min_duration);
+    }
+
+    /// Sets the currency of the prices. Prices set before are not converted,
+    /// so they should be set again in the new currency.
+    #[only_owner]
+    #[endpoint]
+    fn set_price_currency(&self, currency: PriceCurrency) {
+        self.current_currency().set(currency);
+    }
+
+    /// Accepts `token` for payments, at `rate` units of the token, in its
+    /// smallest unit, for one EGLD.
+    #[only_owner]
+    #[endpoint]
+    fn set_token_rate(&self, token: EgldOrEsdtTokenIdentifier, rate: BigUint) {
+        require!(!token.is_egld(), "EGLD has no rate!");
+        require!(token.is_valid(), "Invalid token!");
+        require!(rate > 0, "Rate should be a positive number");
+
+        self.token_rates().insert(token, rate);
+    }
+
+    /// Stops accepting `token` for payments.
+    #[only_owner]
+    #[endpoint]
+    fn remove_token_rate(&self, token: EgldOrEsdtTokenIdentifier) {
+        require!(
+            self.token_rates().remove(&token).is_some(),
+            "Token is not accepted!"
+        );
+    }
+
     #[storage_mapper("price_less")]
     fn price_less(&self) -> SingleValueMapper<BigUint>;
 
     #[storage_mapper("price_more")]
     fn price_more(&self) -> SingleValueMapper<BigUint>;
 
-    #[storage_mapper("price_mid")]
-    fn price_mid(&self) -> VecMapper<PriceItem<Self::Api>>;
+    #[storage_mapper("price_tiers")]
+    fn price_tiers(&self) -> VecMapper<PriceTier<Self::Api>>;
 
     #[storage_mapper("current_type")]
     fn current_type(&self) -> SingleValueMapper<PriceType>;
 
+    #[storage_mapper("current_currency")]
+    fn current_currency(&self) -> SingleValueMapper<PriceCurrency>;
+
     #[storage_mapper("price_fixed")]
     fn price_fixed(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("discounts")]
+    fn discounts(&self) -> VecMapper<DiscountItem>;
+
+    #[storage_mapper("min_duration")]
+    fn min_duration(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("token_rates")]
+    fn token_rates(&self) -> MapMapper<EgldOrEsdtTokenIdentifier, BigUint>;
 }
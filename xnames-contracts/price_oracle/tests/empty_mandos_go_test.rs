@@ -1,4 +1,24 @@
 #[test]
 fn empty_go() {
-    elrond_wasm_debug::mandos_go("mandos/empty.scen.json");
+    elrond_wasm_debug::mandos_go("mandos/price_oracle.scen.json");
+}
+
+#[test]
+fn usd_pricing_go() {
+    elrond_wasm_debug::mandos_go("mandos/usd_pricing.scen.json");
+}
+
+#[test]
+fn feeders_go() {
+    elrond_wasm_debug::mandos_go("mandos/feeders.scen.json");
+}
+
+#[test]
+fn duration_go() {
+    elrond_wasm_debug::mandos_go("mandos/duration.scen.json");
+}
+
+#[test]
+fn label_pricing_go() {
+    elrond_wasm_debug::mandos_go("mandos/label_pricing.scen.json");
 }
@@ -3,11 +3,31 @@ use elrond_wasm_debug::*;
 fn world() -> BlockchainMock {
     let mut blockchain = BlockchainMock::new();
 
-    blockchain.register_contract("file:output/empty.wasm", price_oracle::ContractBuilder);
+    blockchain.register_contract("file:output/price_oracle.wasm", price_oracle::ContractBuilder);
     blockchain
 }
 
 #[test]
 fn empty_rs() {
-    elrond_wasm_debug::mandos_rs("mandos/empty.scen.json", world());
+    elrond_wasm_debug::mandos_rs("mandos/price_oracle.scen.json", world());
+}
+
+#[test]
+fn usd_pricing_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/usd_pricing.scen.json", world());
+}
+
+#[test]
+fn feeders_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/feeders.scen.json", world());
+}
+
+#[test]
+fn duration_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/duration.scen.json", world());
+}
+
+#[test]
+fn label_pricing_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/label_pricing.scen.json", world());
 }
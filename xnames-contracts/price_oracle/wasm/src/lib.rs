@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                            2
+// Endpoints:                           29
 // Async Callback (empty):               1
-// Total number of exported functions:   4
+// Total number of exported functions:  31
 
 #![no_std]
 
@@ -15,7 +15,34 @@ elrond_wasm_node::wasm_endpoints! {
     price_oracle
     (
         get_price
+        get_price_tiers
+        get_price_for_duration
+        get_discounts
+        get_min_duration
+        convert_egld
+        get_token_rates
+        get_usd_and_egld_price
         set_price
+        set_discounts
+        set_min_duration
+        set_price_currency
+        set_token_rate
+        remove_token_rate
+        add_feeder
+        remove_feeder
+        set_quorum
+        set_max_deviation
+        set_max_rate_age
+        push_usd_rate
+        get_usd_rate
+        get_feeders
+        get_submissions
+        set_class_multiplier
+        set_premium_label
+        remove_premium_label
+        get_character_class
+        get_class_multipliers
+        get_premium_labels
     )
 }
 
@@ -0,0 +1,122 @@
+use super::*;
+
+#[elrond_wasm::module]
+pub trait Approval: storage::Storage + validation::Validation {
+    /// Function to approve an operator to transfer a specific domain. Passing
+    /// the owner address clears the approval.
+    ///
+    /// It rejects if:
+    /// - The domain does not exist.
+    /// - The caller is neither the owner of the domain nor an operator for
+    ///   all domains of the owner.
+    #[endpoint(approve)]
+    fn approve(&self, domain: ManagedBuffer, operator: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+
+        let domain = self.normalize_domain(&domain);
+        let token_data = self.domain_details(&domain);
+
+        require!(
+            token_data.owner.eq(&caller)
+                || self.is_approved_for_all(token_data.owner.clone(), caller),
+            "Error: caller must be the owner of the domain or an approved operator!"
+        );
+
+        if operator.eq(&token_data.owner) {
+            self.token_approval(&domain).clear();
+        } else {
+            self.token_approval(&domain).set(&operator);
+        }
+
+        // Logging event
+        self.approval_event(&domain, &token_data.owner, &operator);
+    }
+
+    /// Function to approve or revoke an operator to transfer all domains of
+    /// the caller.
+    ///
+    /// It rejects if:
+    /// - The operator is the caller.
+    #[endpoint(setApprovalForAll)]
+    fn set_approval_for_all(&self, operator: ManagedAddress, approved: bool) {
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            !operator.eq(&caller),
+            "Error: caller cannot be its own operator!"
+        );
+
+        if approved {
+            self.operator_approvals(&caller).insert(operator.clone());
+        } else {
+            self.operator_approvals(&caller).swap_remove(&operator);
+        }
+
+        // Logging event
+        self.approval_for_all_event(&caller, &operator, approved);
+    }
+
+    /// Function to get the operator approved for a specific domain.
+    #[view(getApproved)]
+    fn get_approved(&self, domain: ManagedBuffer) -> OptionalValue<ManagedAddress> {
+        let mapper = self.token_approval(&self.normalize_domain(&domain));
+
+        if mapper.is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(mapper.get())
+        }
+    }
+
+    /// Function to check whether an operator is approved for all domains of
+    /// an owner.
+    #[view(isApprovedForAll)]
+    fn is_approved_for_all(&self, owner: ManagedAddress, operator: ManagedAddress) -> bool {
+        self.operator_approvals(&owner).contains(&operator)
+    }
+
+    // private
+
+    fn is_owner_or_approved(
+        &self,
+        domain: &ManagedBuffer,
+        owner: &ManagedAddress,
+        address: &ManagedAddress,
+    ) -> bool {
+        let token_approval = self.token_approval(domain);
+
+        owner.eq(address)
+            || (!token_approval.is_empty() && token_approval.get().eq(address))
+            || self.operator_approvals(owner).contains(address)
+    }
+
+    fn clear_approval(&self, domain: &ManagedBuffer) {
+        self.token_approval(domain).clear();
+    }
+
+    // storage
+
+    #[storage_mapper("tokenApproval")]
+    fn token_approval(&self, domain: &ManagedBuffer) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("operatorApprovals")]
+    fn operator_approvals(&self, owner: &ManagedAddress) -> UnorderedSetMapper<ManagedAddress>;
+
+    // events
+
+    #[event("approval")]
+    fn approval_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] operator: &ManagedAddress,
+    );
+
+    #[event("approvalForAll")]
+    fn approval_for_all_event(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] operator: &ManagedAddress,
+        approved: bool,
+    );
+}
@@ -0,0 +1,219 @@
+use super::*;
+
+#[elrond_wasm::module]
+pub trait AuctionModule:
+    storage::Storage + validation::Validation + authority::Authority + reserved::ReservedNames
+{
+    /// Function to open an ascending auction for a domain. The winner is
+    /// minted the domain for `duration` seconds once the auction is settled.
+    ///
+    /// It rejects if:
+    /// - If caller is not one of the admins.
+    /// - The domain is blocked.
+    /// - The domain is not available or is already being auctioned.
+    /// - The auction end is not in the future.
+    #[endpoint(openAuction)]
+    fn open_auction(
+        &self,
+        domain: ManagedBuffer,
+        token_id: TokenIdentifier,
+        duration: u64,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        min_bid: BigUint,
+        end: u64,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let slot_time = self.blockchain().get_block_timestamp();
+
+        require!(
+            self.has_admin_rights(&caller),
+            "Unauthorized admin rights by the caller address!"
+        );
+        require!(
+            payment_token.is_valid(),
+            "Invalid payment_token arg, not a valid token ID"
+        );
+        require!(end > slot_time, "Error: auction end must be in the future!");
+
+        let domain = self.normalize_domain(&domain);
+        require!(
+            !matches!(
+                self.name_restrictions().get(&domain),
+                Some(NameRestriction::Blocked)
+            ),
+            "Error: domain is blocked!"
+        );
+        require!(
+            !self.auctions().contains_key(&domain),
+            "Error: domain is already being auctioned!"
+        );
+        require!(
+            !self.is_registered(&domain),
+            "Error: domain is already registered!"
+        );
+
+        self.auctions().insert(
+            domain.clone(),
+            Auction {
+                token_id,
+                duration,
+                payment_token,
+                min_bid,
+                end,
+                highest_bidder: ManagedAddress::zero(),
+                highest_bid: BigUint::zero(),
+            },
+        );
+
+        // Logging event
+        self.open_auction_event(&domain, &end);
+    }
+
+    /// Function to bid on a domain auction. The payment is escrowed and the
+    /// bid of the previous highest bidder is credited back to it, to be
+    /// withdrawn with `withdrawBid`.
+    ///
+    /// It rejects if:
+    /// - The domain is not being auctioned or the auction has ended.
+    /// - The payment token is not the auction payment token.
+    /// - The bid is lower than the minimum bid or not higher than the
+    ///   highest bid.
+    #[payable("*")]
+    #[endpoint(bid)]
+    fn bid(&self, domain: ManagedBuffer) {
+        let caller = self.blockchain().get_caller();
+        let slot_time = self.blockchain().get_block_timestamp();
+        let payment = self.call_value().egld_or_single_esdt();
+
+        let domain = self.normalize_domain(&domain);
+        let auction = self
+            .auctions()
+            .get(&domain)
+            .expect("Error: domain is not being auctioned!");
+
+        require!(auction.end > slot_time, "Error: auction has ended!");
+        require!(
+            payment.token_identifier == auction.payment_token && payment.token_nonce == 0,
+            "Invalid token used as payment"
+        );
+        require!(
+            payment.amount >= auction.min_bid,
+            "Error: bid is lower than the minimum bid!"
+        );
+        require!(
+            payment.amount > auction.highest_bid,
+            "Error: bid must be higher than the highest bid!"
+        );
+
+        // Crediting the refund of the outbid bidder
+        if auction.highest_bid > 0 {
+            self.bid_refunds(&auction.highest_bidder, &auction.payment_token)
+                .update(|refund| *refund += &auction.highest_bid);
+        }
+
+        let amount = payment.amount;
+        self.auctions().entry(domain.clone()).and_modify(|auction| {
+            auction.highest_bidder = caller.clone();
+            auction.highest_bid = amount.clone();
+        });
+
+        // Logging event
+        self.bid_event(&domain, &caller, &amount);
+    }
+
+    /// Function to send the outbid bids of the caller in a token back to it.
+    ///
+    /// It rejects if:
+    /// - There are no outbid bids to withdraw.
+    #[endpoint(withdrawBid)]
+    fn withdraw_bid(&self, token: EgldOrEsdtTokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+
+        let amount = self.bid_refunds(&caller, &token).take();
+        require!(amount > 0, "Error: no bids to withdraw!");
+
+        self.send().direct(&caller, &token, 0, &amount);
+
+        // Logging event
+        self.withdraw_bid_event(&caller, &token, &amount);
+    }
+
+    /// View the outbid bids of a bidder in a token, waiting to be withdrawn.
+    #[view(getBidRefund)]
+    fn get_bid_refund(&self, bidder: ManagedAddress, token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        self.bid_refunds(&bidder, &token).get()
+    }
+
+    /// View the auction of a domain.
+    #[view(getAuction)]
+    fn get_auction(&self, domain: ManagedBuffer) -> OptionalValue<Auction<Self::Api>> {
+        self.auctions().get(&self.normalize_domain(&domain)).into()
+    }
+
+    // private
+
+    /// Removes an ended auction so that it can be settled.
+    ///
+    /// It rejects if:
+    /// - The domain is not being auctioned or the auction has not ended.
+    /// - The caller is neither the highest bidder nor one of the admins.
+    fn take_ended_auction(&self, domain: &ManagedBuffer) -> Auction<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let slot_time = self.blockchain().get_block_timestamp();
+
+        let auction = self
+            .auctions()
+            .get(domain)
+            .expect("Error: domain is not being auctioned!");
+
+        require!(auction.end <= slot_time, "Error: auction has not ended!");
+        require!(
+            auction.highest_bidder.eq(&caller) || self.has_admin_rights(&caller),
+            "Error: caller must be the highest bidder or an admin!"
+        );
+
+        self.auctions().remove(domain);
+        auction
+    }
+
+    // storage
+
+    #[storage_mapper("auctions")]
+    fn auctions(&self) -> MapMapper<ManagedBuffer, Auction<Self::Api>>;
+
+    #[storage_mapper("bidRefunds")]
+    fn bid_refunds(
+        &self,
+        bidder: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    // events
+
+    #[event("openAuction")]
+    fn open_auction_event(&self, #[indexed] domain: &ManagedBuffer, end: &u64);
+
+    #[event("bid")]
+    fn bid_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] bidder: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("withdrawBid")]
+    fn withdraw_bid_event(
+        &self,
+        #[indexed] bidder: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("settleAuction")]
+    fn settle_auction_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] winner: &ManagedAddress,
+        amount: &BigUint,
+    );
+}
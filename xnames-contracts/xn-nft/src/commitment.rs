@@ -0,0 +1,85 @@
+use super::*;
+
+#[elrond_wasm::module]
+pub trait Commitment: storage::Storage + validation::Validation {
+    /// Function to commit to a domain registration without revealing the
+    /// domain. The hash is computed by `getCommitmentHash` and the
+    /// registration is completed through `reveal` once the commitment is
+    /// old enough.
+    ///
+    /// It rejects if:
+    /// - The same commitment was made and has not yet exceeded its maximum age.
+    #[endpoint(commit)]
+    fn commit(&self, hash: ManagedByteArray<Self::Api, 32>) {
+        let slot_time = self.blockchain().get_block_timestamp();
+        let state = self.state().get();
+
+        let commitment = self.commitments(&hash);
+        require!(
+            commitment.is_empty()
+                || commitment
+                    .get()
+                    .checked_add(state.max_commitment_age)
+                    .expect("Error while adding maximum commitment age!")
+                    < slot_time,
+            "Error: commitment already exists!"
+        );
+
+        commitment.set(slot_time);
+    }
+
+    /// Function to compute the commitment hash of a domain registration, as
+    /// the sha256 of the normalized domain, the owner address and the secret.
+    #[view(getCommitmentHash)]
+    fn get_commitment_hash(
+        &self,
+        domain: ManagedBuffer,
+        owner: ManagedAddress,
+        secret: ManagedBuffer,
+    ) -> ManagedByteArray<Self::Api, 32> {
+        let mut data = self.normalize_domain(&domain);
+        data.append(owner.as_managed_buffer());
+        data.append(&secret);
+
+        self.crypto().sha256(&data)
+    }
+
+    // private
+
+    /// Consumes the commitment of `owner` to register `domain`.
+    ///
+    /// It rejects if:
+    /// - The commitment does not exist.
+    /// - The commitment is younger than the minimum or older than the maximum
+    ///   commitment age.
+    fn consume_commitment(
+        &self,
+        domain: &ManagedBuffer,
+        owner: &ManagedAddress,
+        secret: ManagedBuffer,
+    ) {
+        let slot_time = self.blockchain().get_block_timestamp();
+        let state = self.state().get();
+
+        let hash = self.get_commitment_hash(domain.clone(), owner.clone(), secret);
+        let commitment = self.commitments(&hash);
+        require!(!commitment.is_empty(), "Error: commitment does not exist!");
+
+        let age = slot_time - commitment.get();
+        require!(
+            age >= state.min_commitment_age,
+            "Error: commitment is too recent!"
+        );
+        require!(
+            age <= state.max_commitment_age,
+            "Error: commitment has expired!"
+        );
+
+        commitment.clear();
+    }
+
+    // storage
+
+    #[storage_mapper("commitments")]
+    fn commitments(&self, hash: &ManagedByteArray<Self::Api, 32>) -> SingleValueMapper<u64>;
+}
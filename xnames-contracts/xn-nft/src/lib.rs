@@ -3,21 +3,56 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
-mod authority;
-mod nft_module;
-mod structs;
+pub mod approval;
+pub mod auction;
+pub mod authority;
+pub mod commitment;
+pub mod nft_module;
+pub mod premium;
+pub mod referral;
+pub mod reserved;
+pub mod resolver;
+pub mod storage;
+pub mod structs;
+pub mod subdomain;
+pub mod treasury;
+pub mod validation;
 
 pub use crate::structs::*;
 use elrond_wasm::types::heap::Vec;
 
+const NUM_SHARDS: u8 = 3;
+
 #[elrond_wasm::contract]
-pub trait NftMinter: nft_module::NftModule + authority::Authority {
+pub trait NftMinter:
+    nft_module::NftModule
+    + authority::Authority
+    + storage::Storage
+    + validation::Validation
+    + resolver::Resolver
+    + subdomain::Subdomain
+    + commitment::Commitment
+    + premium::Premium
+    + auction::AuctionModule
+    + reserved::ReservedNames
+    + approval::Approval
+    + treasury::Treasury
+    + referral::Referral
+{
     #[init]
     fn init(&self, params: State<Self::Api>) {
+        self.require_valid_royalty(&params.royalty);
         self.state().set(params);
         self.admins().insert(self.blockchain().get_caller());
     }
 
+    /// Function to mint a domain NFT and put it up for sale at
+    /// `selling_price` on behalf of the admins, bypassing the commitment and
+    /// the payment of the registration.
+    ///
+    /// It rejects if:
+    /// - If caller is not one of the admins.
+    /// - The domain is reserved or blocked.
     #[allow(clippy::too_many_arguments)]
     #[allow(clippy::redundant_closure)]
     #[endpoint(createNft)]
@@ -28,8 +63,17 @@ pub trait NftMinter: nft_module::NftModule + authority::Authority {
         selling_price: BigUint,
         opt_token_used_as_payment: OptionalValue<TokenIdentifier>,
         opt_token_used_as_payment_nonce: OptionalValue<u64>,
-        params: MintParams<Self::Api>,
+        mut params: MintParams<Self::Api>,
     ) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.has_admin_rights(&caller),
+            "Unauthorized admin rights by the caller address!"
+        );
+
+        params.domain = self.normalize_domain(&params.domain);
+        self.require_unrestricted(&params.domain);
+
         let token_used_as_payment = match opt_token_used_as_payment {
             OptionalValue::Some(token) => EgldOrEsdtTokenIdentifier::esdt(token),
             OptionalValue::None => EgldOrEsdtTokenIdentifier::egld(),
@@ -48,57 +92,268 @@ pub trait NftMinter: nft_module::NftModule + authority::Authority {
             }
         };
 
-        let expiry = self
-            .blockchain()
-            .get_block_timestamp()
-            .checked_add(params.duration)
-            .expect("Error while adding duration to current timestamp!");
-
         let state = self.state().get();
-        let caller = self.blockchain().get_caller();
         let token_id = params.token_id.clone();
+        let domain = params.domain.clone();
 
         // Storing token details
-        self.token_details().insert(
-            token_id.clone(),
-            TokenData::new(
-                caller.clone(),
-                expiry,
-                state.grace,
-                params.domain.clone(),
-                state.royalty.clone(),
-            ),
-        );
+        self.store_token_details(&caller, &params);
 
-        self.create_nft_with_attributes(
+        let nft_nonce = self.create_nft_with_attributes(
             name,
             state.royalty,
-            token_id.clone(),
+            token_id,
             params,
             uri,
             selling_price,
             token_used_as_payment,
             token_used_as_payment_nonce,
         );
+        self.bind_nft(&domain, nft_nonce);
+
+        // Logging mint event
+        self.mint_event(&domain, &caller, &1);
+    }
+
+    /// Function to register a domain previously committed to with `commit`,
+    /// against a payment priced by the price oracle plus the premium of a
+    /// recently released domain, in EGLD or any token accepted by the price
+    /// oracle. The domain NFT is sent to the caller and the
+    /// payment is split in the treasury, after the commission of the optional
+    /// `referrer`.
+    ///
+    /// It rejects if:
+    /// - The caller is the referrer.
+    /// - The duration is zero.
+    /// - The caller has no commitment for the domain and secret, or the
+    ///   commitment is too recent or expired.
+    /// - The payment token is not accepted by the price oracle.
+    /// - The payment is less or more than the price of the domain for the
+    ///   requested duration.
+    #[payable("*")]
+    #[endpoint(reveal)]
+    fn reveal(
+        &self,
+        name: ManagedBuffer,
+        uri: ManagedBuffer,
+        mut params: MintParams<Self::Api>,
+        secret: ManagedBuffer,
+        opt_referrer: OptionalValue<ManagedAddress>,
+    ) {
+        params.domain = self.normalize_domain(&params.domain);
+        self.require_unrestricted(&params.domain);
+        require!(params.duration > 0, "Error: duration must not be zero!");
+
+        let caller = self.blockchain().get_caller();
+        self.consume_commitment(&params.domain, &caller, secret);
+
+        let (payment_token, payment) = self.call_value().egld_or_single_fungible_esdt();
+        let price = self.get_registration_price(
+            params.domain.clone(),
+            params.duration,
+            OptionalValue::Some(payment_token.clone()),
+        );
+
+        self.require_exact_payment(&payment, &price);
+
+        let state = self.state().get();
+        let token_id = params.token_id.clone();
+        let domain = params.domain.clone();
+
+        // Storing token details
+        self.store_token_details(&caller, &params);
+
+        let nft_nonce =
+            self.mint_nft_with_attributes(name, state.royalty, token_id.clone(), params, uri);
+        self.bind_nft(&domain, nft_nonce);
+        self.send_nft(&caller, &token_id, nft_nonce);
+
+        // Paying the referral commission and splitting the rest in the treasury
+        let fees = self.pay_referral(
+            &opt_referrer.into_option(),
+            &caller,
+            &payment_token,
+            &payment,
+        );
+        self.deposit_fees(&payment_token, &fees);
+
+        // Logging mint event
+        self.mint_event(&domain, &caller, &1);
+    }
+
+    /// Function to assign a reserved domain directly to `params.owner`.
+    ///
+    /// It rejects if:
+    /// - If caller is not one of the admins.
+    /// - The domain is not reserved.
+    /// - The domain is not available.
+    #[endpoint(assignReservedName)]
+    fn assign_reserved_name(
+        &self,
+        name: ManagedBuffer,
+        uri: ManagedBuffer,
+        mut params: MintParams<Self::Api>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.has_admin_rights(&caller),
+            "Unauthorized admin rights by the caller address!"
+        );
+
+        params.domain = self.normalize_domain(&params.domain);
+        require!(
+            matches!(
+                self.name_restrictions().get(&params.domain),
+                Some(NameRestriction::Reserved)
+            ),
+            "Error: domain is not reserved!"
+        );
+
+        let state = self.state().get();
+        let owner = params.owner.clone();
+        let token_id = params.token_id.clone();
+        let domain = params.domain.clone();
+
+        // Storing token details
+        self.store_token_details(&owner, &params);
+
+        let nft_nonce =
+            self.mint_nft_with_attributes(name, state.royalty, token_id.clone(), params, uri);
+        self.bind_nft(&domain, nft_nonce);
+        self.send_nft(&owner, &token_id, nft_nonce);
 
         // Logging mint event
-        self.mint_event(&token_id, &caller, &1);
+        self.mint_event(&domain, &owner, &1);
+    }
+
+    /// Function to settle an ended domain auction. The domain is minted to the
+    /// highest bidder and the winning bid is split in the treasury.
+    /// An auction without bids is simply closed.
+    ///
+    /// It rejects if:
+    /// - The domain is not being auctioned or the auction has not ended.
+    /// - The caller is neither the highest bidder nor one of the admins.
+    #[endpoint(settleAuction)]
+    fn settle_auction(&self, domain: ManagedBuffer, name: ManagedBuffer, uri: ManagedBuffer) {
+        let domain = self.normalize_domain(&domain);
+        let auction = self.take_ended_auction(&domain);
+
+        if auction.highest_bid == 0 {
+            return;
+        }
+
+        let state = self.state().get();
+        let winner = auction.highest_bidder;
+        let token_id = auction.token_id;
+        let params = MintParams {
+            token_id: token_id.clone(),
+            domain: domain.clone(),
+            owner: winner.clone(),
+            duration: auction.duration,
+        };
+
+        // Storing token details
+        self.store_token_details(&winner, &params);
+
+        let nft_nonce =
+            self.mint_nft_with_attributes(name, state.royalty, token_id.clone(), params, uri);
+        self.bind_nft(&domain, nft_nonce);
+        self.send_nft(&winner, &token_id, nft_nonce);
+
+        // Splitting proceeds in the treasury
+        self.deposit_fees(&auction.payment_token, &auction.highest_bid);
+
+        // Logging events
+        self.settle_auction_event(&domain, &winner, &auction.highest_bid);
+        self.mint_event(&domain, &winner, &1);
+    }
+
+    /// Function to renew a domain subscription, extending its expiry by
+    /// `duration` seconds from the later of the current expiry and now, against
+    /// a payment in EGLD or any token accepted by the price oracle. The payment
+    /// is split in the treasury, after the commission of the optional
+    /// `referrer`.
+    ///
+    /// It rejects if:
+    /// - The caller is the referrer.
+    /// - The duration is zero.
+    /// - The domain does not exist.
+    /// - The subscription has expired past its grace period.
+    /// - The payment token is not accepted by the price oracle.
+    /// - The payment is less or more than the price of the domain for the
+    ///   requested duration.
+    #[payable("*")]
+    #[endpoint(renew)]
+    fn renew(
+        &self,
+        domain: ManagedBuffer,
+        duration: u64,
+        opt_referrer: OptionalValue<ManagedAddress>,
+    ) {
+        require!(duration > 0, "Error: duration must not be zero!");
+
+        let slot_time = self.blockchain().get_block_timestamp();
+        let caller = self.blockchain().get_caller();
+
+        let domain = self.normalize_domain(&domain);
+        let token_data = self.domain_details(&domain);
+
+        let status = SubscriptionData {
+            owner: token_data.owner.clone(),
+            expiry: token_data.expiry,
+            grace: token_data.grace,
+        }
+        .into_status(slot_time)
+        .status;
+        require!(
+            !matches!(status, SubscriptionExpiryStatus::Expired),
+            "Error: subscription has expired!"
+        );
+
+        let (payment_token, payment) = self.call_value().egld_or_single_fungible_esdt();
+        let price = self.get_renewal_price(
+            token_data.domain.clone(),
+            duration,
+            OptionalValue::Some(payment_token.clone()),
+        );
+        self.require_exact_payment(&payment, &price);
+
+        let expiry = token_data
+            .expiry
+            .max(slot_time)
+            .checked_add(duration)
+            .expect("Error while adding duration to expiry!");
+
+        self.token_details()
+            .entry(domain.clone())
+            .and_modify(|token_data| token_data.expiry = expiry);
+
+        // Paying the referral commission and splitting the rest in the treasury
+        let fees = self.pay_referral(
+            &opt_referrer.into_option(),
+            &caller,
+            &payment_token,
+            &payment,
+        );
+        self.deposit_fees(&payment_token, &fees);
+
+        // Logging renew event
+        self.renew_event(&domain, &caller, &expiry);
     }
 
     /// Function to burn token.
     ///
     /// It rejects if:
+    /// - The domain does not exist.
     /// - Current Time is less than expiry + Grace Period
     #[allow(clippy::too_many_arguments)]
     #[allow(clippy::redundant_closure)]
     #[endpoint(burn)]
-    fn burn(&self, token_id: TokenIdentifier<Self::Api>) {
+    fn burn(&self, domain: ManagedBuffer) {
         let slot_time = self.blockchain().get_block_timestamp();
 
-        let token_data = self
-            .token_details()
-            .get(&token_id)
-            .expect("Token Id does not exists!");
+        let domain = self.normalize_domain(&domain);
+        let token_data = self.domain_details(&domain);
 
         // Checking token validation
         require!(
@@ -111,41 +366,52 @@ pub trait NftMinter: nft_module::NftModule + authority::Authority {
         );
 
         // Burning token data
-        let token_data = self
-            .token_details()
-            .remove(&token_id)
-            .expect("Error while burning token data!");
-
-        // Logging burn event
-        self.burn_event(&token_id, &token_data.owner, &1);
+        self.burn_token_data(&domain);
     }
 
-    /// Execute a list of domain transfers, in the order of the list.
+    /// Execute a list of domain transfers, in the order of the list. The
+    /// domain NFTs sent along with the call are forwarded to the receivers,
+    /// so that NFT holdership and domain ownership stay in sync. Smart
+    /// contract receivers get the NFT through their `onDomainReceived` hook,
+    /// together with the transfer `data`. The hook is called synchronously,
+    /// so that a rejection reverts the whole transfer, which only works for
+    /// receivers in the same shard as this contract.
+    /// Approved operators, such as a marketplace holding the NFT in escrow,
+    /// transfer a domain on behalf of its owner by sending its NFT as well.
     ///
     /// It rejects if:
     /// - Any of the transfers fail to be executed, which could be if:
-    ///     - The `token_id` does not exist.
+    ///     - The domain does not exist.
     ///     - The caller is not the owner of the token, or an operator for this
-    ///       specific `token_id` and `from` address.
+    ///       specific domain and `from` address.
     ///     - The token is not owned by the `from` address.
+    ///     - The domain NFT is not part of the payment.
+    ///     - The domain subscription has expired.
+    ///     - A smart contract receiver is in another shard, or rejects the
+    ///       domain.
+    /// - Any of the payments is not the NFT of one of the transfers.
     /// - Fails to log event.
     #[allow(clippy::too_many_arguments)]
     #[allow(clippy::redundant_closure)]
+    #[payable("*")]
     #[endpoint(transfer)]
     fn transfer(&self, transfers: Vec<Transfer<Self::Api>>) {
         let caller = self.blockchain().get_caller();
         let slot_time = self.blockchain().get_block_timestamp();
+        let mut payments = self.call_value().all_esdt_transfers();
 
         for transfer in transfers {
             // Check owner from storage
-            let token_data = self
-                .token_details()
-                .get(&transfer.token_id)
-                .expect("Token Id does not exists!");
+            let domain = self.normalize_domain(&transfer.domain);
+            let token_data = self.domain_details(&domain);
 
             require!(
-                token_data.owner.eq(&transfer.from) && token_data.owner.eq(&caller),
-                "Error: caller must be the owner of the domain!"
+                token_data.owner.eq(&transfer.from),
+                "Error: domain is not owned by the from address!"
+            );
+            require!(
+                self.is_owner_or_approved(&domain, &token_data.owner, &caller),
+                "Error: caller must be the owner of the domain or an approved operator!"
             );
 
             // Check the transfer amount
@@ -155,38 +421,101 @@ pub trait NftMinter: nft_module::NftModule + authority::Authority {
                 _ => sc_panic!("Error: operation not permitted!"),
             }
 
+            // Check the domain NFT is being transferred, consuming its payment
+            let payment_index = payments
+                .iter()
+                .position(|payment| {
+                    payment.token_identifier == token_data.token_id
+                        && payment.token_nonce == token_data.nft_nonce
+                        && payment.amount == 1u32
+                })
+                .unwrap_or_else(|| sc_panic!("Error: domain NFT must be sent with the transfer!"));
+            payments.remove(payment_index);
+
             // Check token expiry.
             require!(
                 token_data
                     .expiry
                     .checked_add(token_data.grace)
                     .expect("Error while adding expiry and grace periods!")
-                    < slot_time,
+                    >= slot_time,
                 "Error Grace period must not be over!"
             );
 
             require!(
-                token_data.expiry < slot_time,
+                token_data.expiry >= slot_time,
                 "Error expiry period must not be over!"
             );
 
+            // Update token data
+            self.change_owner(&domain, &transfer.to);
+
+            // Moving the domain NFT, notifying smart contract receivers
+            if self.blockchain().is_smart_contract(&transfer.to) {
+                require!(
+                    self.shard_of(&transfer.to)
+                        == self.shard_of(&self.blockchain().get_sc_address()),
+                    "Error: receiver must be in the same shard!"
+                );
+
+                let accepted: bool = self
+                    .domain_receiver_proxy(transfer.to.clone())
+                    .on_domain_received(
+                        domain.clone(),
+                        transfer.from.clone(),
+                        ManagedBuffer::new_from_bytes(&transfer.data),
+                    )
+                    .with_esdt_transfer((
+                        token_data.token_id.clone(),
+                        token_data.nft_nonce,
+                        BigUint::from(1u32),
+                    ))
+                    .execute_on_dest_context();
+
+                require!(accepted, "Error: receiver rejected the domain!");
+            } else {
+                self.send_nft(&transfer.to, &token_data.token_id, token_data.nft_nonce);
+            }
+
             // Logging event
-            // TODO: Move logging at the end of the owner update. Logging
-            // before actual move to avoid rust's error[E0382] `use of
-            // partially moved value in closure`.
-            self.transfer_event(
-                &transfer.token_id,
-                &transfer.from,
-                &transfer.to,
-                &transfer.amount,
-            );
+            self.transfer_event(&domain, &transfer.from, &transfer.to, &transfer.amount);
+        }
 
-            // Update token data
-            let to_address = transfer.to;
-            self.token_details()
-                .entry(transfer.token_id)
-                .and_modify(|token_data| token_data.owner = to_address);
+        require!(
+            payments.is_empty(),
+            "Error: payments must be the NFTs of the transfers!"
+        );
+    }
+
+    /// Function to claim ownership of a domain by sending its NFT, which is
+    /// returned to the caller. This syncs the domain owner after the NFT was
+    /// moved outside of the contract, e.g. sold on a marketplace.
+    ///
+    /// It rejects if:
+    /// - The payment is not the NFT of a domain.
+    #[payable("*")]
+    #[endpoint(claimOwnership)]
+    fn claim_ownership(&self) {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+
+        let domain = self.nft_domain(&payment.token_identifier, payment.token_nonce);
+        require!(
+            !domain.is_empty() && payment.amount == 1u32,
+            "Error: payment is not a domain NFT!"
+        );
+
+        let domain = domain.get();
+        let token_data = self.domain_details(&domain);
+        if token_data.owner != caller {
+            self.change_owner(&domain, &caller);
+
+            // Logging event
+            self.transfer_event(&domain, &token_data.owner, &caller, &1);
         }
+
+        // Returning the domain NFT
+        self.send_nft(&caller, &payment.token_identifier, payment.token_nonce);
     }
 
     // The marketplace SC will send the funds directly to the initial caller, i.e. the owner
@@ -210,9 +539,15 @@ pub trait NftMinter: nft_module::NftModule + authority::Authority {
     /// Function to update internal values. This includes:
     /// - Royalty. Fee percentage for token sale. Gets assigned to a token on mint.
     /// - Beneficiary. Account address that receives the fee.
+    /// - Price Oracle. Contract address that quotes domain prices.
+    /// - Min/Max Commitment Age. Seconds a commitment must wait before, and
+    ///   may wait at most until, it is revealed.
+    /// - Premium. Starting premium of a released domain, its half life and
+    ///   the duration after which it drops to zero.
     ///
     ///  It rejects if:
     ///  - If caller is neither one of the admins nor one of the maintainers.
+    ///  - The royalty exceeds 10_000 (100%).
     #[endpoint(updateInternalValue)]
     fn update_internal_value(&self, update_params: UpdateInternalValueParams<Self::Api>) {
         let caller = self.blockchain().get_caller();
@@ -224,39 +559,174 @@ pub trait NftMinter: nft_module::NftModule + authority::Authority {
 
         match update_params {
             UpdateInternalValueParams::Royalty(percentage) => {
+                self.require_valid_royalty(&percentage);
                 self.state().update(|state| state.royalty = percentage)
             }
             UpdateInternalValueParams::Beneficiary(account) => {
                 self.state().update(|state| state.benificiary = account)
             }
+            UpdateInternalValueParams::PriceOracle(address) => {
+                self.state().update(|state| state.price_oracle = address)
+            }
+            UpdateInternalValueParams::MinCommitmentAge(age) => {
+                self.state().update(|state| state.min_commitment_age = age)
+            }
+            UpdateInternalValueParams::MaxCommitmentAge(age) => {
+                self.state().update(|state| state.max_commitment_age = age)
+            }
+            UpdateInternalValueParams::StartPremium(premium) => {
+                self.state().update(|state| state.start_premium = premium)
+            }
+            UpdateInternalValueParams::PremiumHalfLife(half_life) => self
+                .state()
+                .update(|state| state.premium_half_life = half_life),
+            UpdateInternalValueParams::PremiumDuration(duration) => self
+                .state()
+                .update(|state| state.premium_duration = duration),
+        }
+    }
+
+    // private
+
+    /// Computes the shard of an address from its last byte, the way the
+    /// protocol assigns addresses to its shards.
+    fn shard_of(&self, address: &ManagedAddress) -> u8 {
+        let last_byte = address.to_byte_array()[31];
+        let shard = last_byte & 0b11;
+
+        if shard < NUM_SHARDS {
+            shard
+        } else {
+            last_byte & 0b01
         }
     }
 
+    /// Stores the details of a newly minted domain and indexes it by its
+    /// normalized name. An expired registration of the same domain is burnt.
+    ///
+    /// It rejects if:
+    /// - The domain is registered and not yet expired.
+    /// - The domain is being auctioned.
+    fn store_token_details(&self, owner: &ManagedAddress, params: &MintParams<Self::Api>) {
+        let domain = self.normalize_domain(&params.domain);
+        require!(
+            !self.is_registered(&domain),
+            "Error: domain is already registered!"
+        );
+        require!(
+            !self.auctions().contains_key(&domain),
+            "Error: domain is being auctioned!"
+        );
+
+        if self.token_details().contains_key(&domain) {
+            self.burn_token_data(&domain);
+        }
+        self.released_at(&domain).clear();
+
+        let expiry = self
+            .blockchain()
+            .get_block_timestamp()
+            .checked_add(params.duration)
+            .expect("Error while adding duration to current timestamp!");
+
+        let state = self.state().get();
+        self.token_details().insert(
+            domain.clone(),
+            TokenData::new(
+                owner.clone(),
+                expiry,
+                state.grace,
+                domain,
+                state.royalty,
+                params.token_id.clone(),
+            ),
+        );
+    }
+
+    /// Binds a domain to the nonce of its newly minted NFT, indexing the
+    /// domain by the NFT.
+    fn bind_nft(&self, domain: &ManagedBuffer, nft_nonce: u64) {
+        let mut token_data = self.domain_details(domain);
+        token_data.nft_nonce = nft_nonce;
+
+        self.nft_domain(&token_data.token_id, nft_nonce).set(domain);
+        self.token_details().insert(domain.clone(), token_data);
+    }
+
+    fn require_valid_royalty(&self, royalty: &BigUint) {
+        require!(
+            royalty <= &nft_module::ROYALTY_DENOMINATOR,
+            "Error: royalty must not exceed 100%!"
+        );
+    }
+
+    fn require_exact_payment(&self, payment: &BigUint, price: &BigUint) {
+        require!(
+            payment >= price,
+            "Error: payment is less than the domain price!"
+        );
+        require!(
+            payment <= price,
+            "Error: payment is more than the domain price!"
+        );
+    }
+
+    fn burn_token_data(&self, domain: &ManagedBuffer) {
+        let token_data = self
+            .token_details()
+            .remove(domain)
+            .expect("Error while burning token data!");
+
+        self.nft_domain(&token_data.token_id, token_data.nft_nonce)
+            .clear();
+        self.clear_approval(domain);
+        self.released_at(&token_data.domain).set(
+            token_data
+                .expiry
+                .checked_add(token_data.grace)
+                .expect("Error while adding expiry and grace periods!"),
+        );
+
+        // Clearing resolver records
+        self.clear_records(&token_data.domain);
+        self.clear_primary_names(&token_data.domain);
+
+        // Clearing subdomains
+        self.clear_subdomains(&token_data.domain);
+
+        // Logging burn event
+        self.burn_event(domain, &token_data.owner, &1);
+    }
+
     #[proxy]
     fn marketplace_proxy(
         &self,
         sc_address: ManagedAddress,
     ) -> nft_marketplace_proxy::Proxy<Self::Api>;
 
-    // storage
+    #[proxy]
+    fn domain_receiver_proxy(
+        &self,
+        sc_address: ManagedAddress,
+    ) -> domain_receiver_proxy::Proxy<Self::Api>;
 
-    #[view]
-    #[storage_mapper("state")]
-    fn state(&self) -> SingleValueMapper<State<Self::Api>>;
+    #[proxy]
+    fn price_oracle_proxy(
+        &self,
+        sc_address: ManagedAddress,
+    ) -> price_oracle_proxy::Proxy<Self::Api>;
 
-    #[view]
-    #[storage_mapper("tokenDetails")]
-    fn token_details(&self) -> MapMapper<TokenIdentifier, TokenData<Self::Api>>;
+    // views
 
     /// Function to get domain subscription status.
     #[view(getTokenSubscriptionStatus)]
     fn get_token_subscription_status(
         &self,
-        token_id: TokenIdentifier,
+        domain: ManagedBuffer,
     ) -> OptionalValue<TokenSubscriptionStatus<Self::Api>> {
         let slot_time = self.blockchain().get_block_timestamp();
 
-        if let Some(token_data) = self.token_details().get(&token_id) {
+        if let Some(token_data) = self.token_details().get(&self.normalize_domain(&domain)) {
             OptionalValue::Some(
                 SubscriptionData {
                     owner: token_data.owner,
@@ -270,17 +740,99 @@ pub trait NftMinter: nft_module::NftModule + authority::Authority {
         }
     }
 
-    /// View token data owned by particular address by token_id.
+    /// Function to get the price of registering a domain for the given
+    /// duration in seconds, including the premium of a recently released
+    /// domain. The price is in `token`, EGLD by default.
+    ///
+    /// It rejects if:
+    /// - The domain is not valid.
+    /// - The token is not accepted by the price oracle.
+    #[view(getRegistrationPrice)]
+    fn get_registration_price(
+        &self,
+        domain: ManagedBuffer,
+        duration: u64,
+        opt_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) -> BigUint {
+        let token = opt_token
+            .into_option()
+            .unwrap_or_else(EgldOrEsdtTokenIdentifier::egld);
+
+        let mut premium = self.get_premium(domain.clone());
+        if premium > 0 && !token.is_egld() {
+            let price_oracle = self.state().get().price_oracle;
+            premium = self
+                .price_oracle_proxy(price_oracle)
+                .convert_egld(premium, token.clone())
+                .execute_on_dest_context();
+        }
+
+        self.get_renewal_price(domain, duration, OptionalValue::Some(token)) + premium
+    }
+
+    /// Function to get the price of a domain for the given duration in seconds.
+    /// The price oracle prices the domain label yearly, prorated over the
+    /// duration with its multi-year discounts. The price is in `token`, EGLD
+    /// by default.
+    ///
+    /// It rejects if:
+    /// - The domain is not valid.
+    /// - The duration is less than the minimum duration of the price oracle.
+    /// - The token is not accepted by the price oracle.
+    #[view(getRenewalPrice)]
+    fn get_renewal_price(
+        &self,
+        domain: ManagedBuffer,
+        duration: u64,
+        opt_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) -> BigUint {
+        let domain = self.normalize_domain(&domain);
+        let token = opt_token
+            .into_option()
+            .unwrap_or_else(EgldOrEsdtTokenIdentifier::egld);
+
+        let price_oracle = self.state().get().price_oracle;
+        self.price_oracle_proxy(price_oracle)
+            .get_price_for_duration(domain, duration, token)
+            .execute_on_dest_context()
+    }
+
+    /// Function to check whether a domain can be registered. A domain whose
+    /// subscription expired past its grace period is available again.
+    #[view(isAvailable)]
+    fn is_available(&self, domain: ManagedBuffer) -> bool {
+        matches!(self.get_availability(domain), DomainAvailability::Available)
+    }
+
+    /// Function to get whether a domain can be registered, or why it cannot.
+    #[view(getAvailability)]
+    fn get_availability(&self, domain: ManagedBuffer) -> DomainAvailability {
+        let domain = self.normalize_domain(&domain);
+
+        if self.is_registered(&domain) {
+            return DomainAvailability::Registered;
+        }
+        if self.auctions().contains_key(&domain) {
+            return DomainAvailability::Auctioned;
+        }
+
+        match self.name_restrictions().get(&domain) {
+            Some(NameRestriction::Reserved) => DomainAvailability::Reserved,
+            Some(NameRestriction::Blocked) => DomainAvailability::Blocked,
+            None => DomainAvailability::Available,
+        }
+    }
+
+    /// View token data of a domain, including the NFT it is bound to.
     #[view(getTokenInfo)]
-    fn get_token_info(&self, token_id: TokenIdentifier) -> OptionalValue<TokenInfo<Self::Api>> {
-        if let Some(token_data) = self.token_details().get(&token_id) {
-            OptionalValue::Some(
-                SubscriptionData {
-                    owner: token_data.owner,
-                    expiry: token_data.expiry,
-                    grace: token_data.grace,
-                }
-            )
+    fn get_token_info(&self, domain: ManagedBuffer) -> OptionalValue<TokenInfo<Self::Api>> {
+        if let Some(token_data) = self.token_details().get(&self.normalize_domain(&domain)) {
+            OptionalValue::Some(TokenInfo {
+                domain: token_data.domain,
+                royalty: token_data.royalty,
+                token_id: token_data.token_id,
+                nft_nonce: token_data.nft_nonce,
+            })
         } else {
             OptionalValue::None
         }
@@ -291,7 +843,7 @@ pub trait NftMinter: nft_module::NftModule + authority::Authority {
     #[event("mint")]
     fn mint_event(
         &self,
-        #[indexed] token_id: &TokenIdentifier,
+        #[indexed] domain: &ManagedBuffer,
         #[indexed] owner: &ManagedAddress,
         amount: &u64,
     );
@@ -299,15 +851,23 @@ pub trait NftMinter: nft_module::NftModule + authority::Authority {
     #[event("burn")]
     fn burn_event(
         &self,
-        #[indexed] token_id: &TokenIdentifier,
+        #[indexed] domain: &ManagedBuffer,
         #[indexed] owner: &ManagedAddress,
         amount: &u64,
     );
 
+    #[event("renew")]
+    fn renew_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] caller: &ManagedAddress,
+        expiry: &u64,
+    );
+
     #[event("transfer")]
     fn transfer_event(
         &self,
-        #[indexed] token_id: &TokenIdentifier,
+        #[indexed] domain: &ManagedBuffer,
         #[indexed] from: &ManagedAddress,
         #[indexed] to: &ManagedAddress,
         amount: &u64,
@@ -328,3 +888,39 @@ mod nft_marketplace_proxy {
         );
     }
 }
+
+mod domain_receiver_proxy {
+    elrond_wasm::imports!();
+
+    /// Hook of the smart contracts receiving domains, called synchronously
+    /// by `transfer`, so receivers must be in the same shard.
+    #[elrond_wasm::proxy]
+    pub trait DomainReceiver {
+        #[payable("*")]
+        #[endpoint(onDomainReceived)]
+        fn on_domain_received(
+            &self,
+            domain: ManagedBuffer,
+            from: ManagedAddress,
+            data: ManagedBuffer,
+        ) -> bool;
+    }
+}
+
+mod price_oracle_proxy {
+    elrond_wasm::imports!();
+
+    #[elrond_wasm::proxy]
+    pub trait PriceOracle {
+        #[view(get_price_for_duration)]
+        fn get_price_for_duration(
+            &self,
+            label: ManagedBuffer,
+            duration: u64,
+            token: EgldOrEsdtTokenIdentifier,
+        ) -> BigUint;
+
+        #[view(convert_egld)]
+        fn convert_egld(&self, amount: BigUint, token: EgldOrEsdtTokenIdentifier) -> BigUint;
+    }
+}
@@ -1,6 +1,7 @@
 use super::*;
 
 const NFT_AMOUNT: u32 = 1;
+pub const ROYALTY_DENOMINATOR: u32 = 10_000;
 
 #[derive(TypeAbi, TopEncode, TopDecode)]
 pub struct PriceTag<M: ManagedTypeApi> {
@@ -10,7 +11,9 @@ pub struct PriceTag<M: ManagedTypeApi> {
 }
 
 #[elrond_wasm::module]
-pub trait NftModule {
+pub trait NftModule:
+    storage::Storage + validation::Validation + approval::Approval + resolver::Resolver
+{
     #[payable("EGLD")]
     #[endpoint(issueToken)]
     fn issue_token(&self, token_name: ManagedBuffer, token_ticker: ManagedBuffer) {
@@ -85,22 +88,55 @@ pub trait NftModule {
 
         let nft_token_id = self.nft_token_id().get();
         let caller = self.blockchain().get_caller();
-        self.send().direct_esdt(
-            &caller,
-            &nft_token_id,
-            nft_nonce,
-            &BigUint::from(NFT_AMOUNT),
-        );
+        self.send_nft(&caller, &nft_token_id, nft_nonce);
+
+        let domain = self.nft_domain(&nft_token_id, nft_nonce);
+        let royalty = if domain.is_empty() {
+            self.state().get().royalty
+        } else {
+            // Making the buyer the owner of the domain
+            let domain = domain.get();
+            self.change_owner(&domain, &caller);
+            self.domain_details(&domain).royalty
+        };
+
+        // Accruing the royalty to the beneficiary
+        let royalty_amount = &payment.amount * &royalty / ROYALTY_DENOMINATOR;
+        self.accumulated_royalties(&payment.token_identifier, payment.token_nonce)
+            .update(|amount| *amount += &royalty_amount);
 
         let owner = self.blockchain().get_owner_address();
         self.send().direct(
             &owner,
             &payment.token_identifier,
             payment.token_nonce,
-            &payment.amount,
+            &(payment.amount - royalty_amount),
         );
     }
 
+    /// Function to withdraw the royalties accumulated in a payment token.
+    ///
+    /// It rejects if:
+    /// - The caller is not the beneficiary.
+    /// - There are no royalties to withdraw.
+    #[endpoint(withdrawRoyalties)]
+    fn withdraw_royalties(&self, token: EgldOrEsdtTokenIdentifier, nonce: u64) {
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            self.state().get().benificiary.eq(&caller),
+            "Error: caller must be the beneficiary!"
+        );
+
+        let amount = self.accumulated_royalties(&token, nonce).take();
+        require!(amount > 0, "Error: no royalties to withdraw!");
+
+        self.send().direct(&caller, &token, nonce, &amount);
+
+        // Logging event
+        self.withdraw_royalties_event(&caller, &token, nonce, &amount);
+    }
+
     // views
 
     #[allow(clippy::type_complexity)]
@@ -119,6 +155,12 @@ pub trait NftModule {
         }
     }
 
+    /// View the royalties accumulated in a payment token.
+    #[view(getAccumulatedRoyalties)]
+    fn get_accumulated_royalties(&self, token: EgldOrEsdtTokenIdentifier, nonce: u64) -> BigUint {
+        self.accumulated_royalties(&token, nonce).get()
+    }
+
     // callbacks
 
     #[callback]
@@ -143,6 +185,20 @@ pub trait NftModule {
 
     // private
 
+    /// Makes `to` the owner of a domain, clearing the approval and the
+    /// primary names given by the previous owner.
+    fn change_owner(&self, domain: &ManagedBuffer, to: &ManagedAddress) {
+        self.token_details()
+            .entry(domain.clone())
+            .and_modify(|token_data| token_data.owner = to.clone());
+
+        // Clearing the approval given by the previous owner
+        self.clear_approval(domain);
+
+        // Invalidating primary names claimed on the domain
+        self.clear_primary_names(domain);
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn create_nft_with_attributes<T: TopEncode>(
         &self,
@@ -154,6 +210,25 @@ pub trait NftModule {
         selling_price: BigUint,
         token_used_as_payment: EgldOrEsdtTokenIdentifier,
         token_used_as_payment_nonce: u64,
+    ) -> u64 {
+        let nft_nonce = self.mint_nft_with_attributes(name, royalties, token_id, attributes, uri);
+
+        self.price_tag(nft_nonce).set(&PriceTag {
+            token: token_used_as_payment,
+            nonce: token_used_as_payment_nonce,
+            amount: selling_price,
+        });
+
+        nft_nonce
+    }
+
+    fn mint_nft_with_attributes<T: TopEncode>(
+        &self,
+        name: ManagedBuffer,
+        royalties: BigUint,
+        token_id: TokenIdentifier,
+        attributes: T,
+        uri: ManagedBuffer,
     ) -> u64 {
         let mut serialized_attributes = ManagedBuffer::new();
         if let core::result::Result::Err(err) = attributes.top_encode(&mut serialized_attributes) {
@@ -163,7 +238,7 @@ pub trait NftModule {
         let attributes_sha256 = self.crypto().sha256(&serialized_attributes);
         let attributes_hash = attributes_sha256.as_managed_buffer();
         let uris = ManagedVec::from_single_item(uri);
-        let nft_nonce = self.send().esdt_nft_create(
+        self.send().esdt_nft_create(
             &token_id,
             &BigUint::from(NFT_AMOUNT),
             &name,
@@ -171,15 +246,12 @@ pub trait NftModule {
             attributes_hash,
             &attributes,
             &uris,
-        );
-
-        self.price_tag(nft_nonce).set(&PriceTag {
-            token: token_used_as_payment,
-            nonce: token_used_as_payment_nonce,
-            amount: selling_price,
-        });
+        )
+    }
 
-        nft_nonce
+    fn send_nft(&self, to: &ManagedAddress, token_id: &TokenIdentifier, nft_nonce: u64) {
+        self.send()
+            .direct_esdt(to, token_id, nft_nonce, &BigUint::from(NFT_AMOUNT));
     }
 
     fn require_token_issued(&self) {
@@ -193,4 +265,22 @@ pub trait NftModule {
 
     #[storage_mapper("priceTag")]
     fn price_tag(&self, nft_nonce: u64) -> SingleValueMapper<PriceTag<Self::Api>>;
+
+    #[storage_mapper("accumulatedRoyalties")]
+    fn accumulated_royalties(
+        &self,
+        token: &EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+    ) -> SingleValueMapper<BigUint>;
+
+    // events
+
+    #[event("withdrawRoyalties")]
+    fn withdraw_royalties_event(
+        &self,
+        #[indexed] beneficiary: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] nonce: u64,
+        amount: &BigUint,
+    );
 }
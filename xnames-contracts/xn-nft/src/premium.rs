@@ -0,0 +1,76 @@
+use super::*;
+
+#[elrond_wasm::module]
+pub trait Premium: storage::Storage + validation::Validation {
+    /// Function to get the premium charged on top of the registration price
+    /// of a released domain. Once a domain passes its expiry and grace period,
+    /// the premium starts at `start_premium` and halves every
+    /// `premium_half_life` seconds, decreasing linearly in between, until it
+    /// drops to zero after `premium_duration` seconds. There is no premium
+    /// while the domain is still registered.
+    #[view(getPremium)]
+    fn get_premium(&self, domain: ManagedBuffer) -> BigUint {
+        let domain = self.normalize_domain(&domain);
+        if self.is_registered(&domain) {
+            return BigUint::zero();
+        }
+
+        match self.release_time(&domain) {
+            Some(released_at) => {
+                let slot_time = self.blockchain().get_block_timestamp();
+                self.compute_premium(released_at, slot_time)
+            }
+            None => BigUint::zero(),
+        }
+    }
+
+    // private
+
+    /// Returns when the domain was, or will be, released, i.e. its expiry plus
+    /// its grace period.
+    fn release_time(&self, domain: &ManagedBuffer) -> Option<u64> {
+        if let Some(token_data) = self.find_domain_details(domain) {
+            return Some(
+                token_data
+                    .expiry
+                    .checked_add(token_data.grace)
+                    .expect("Error while adding expiry and grace periods!"),
+            );
+        }
+
+        let released_at = self.released_at(domain);
+        if released_at.is_empty() {
+            None
+        } else {
+            Some(released_at.get())
+        }
+    }
+
+    fn compute_premium(&self, released_at: u64, slot_time: u64) -> BigUint {
+        let state = self.state().get();
+        let elapsed = slot_time.saturating_sub(released_at);
+
+        if state.premium_half_life == 0 || elapsed >= state.premium_duration {
+            return BigUint::zero();
+        }
+
+        let halvings = elapsed / state.premium_half_life;
+        let remainder = elapsed % state.premium_half_life;
+        let mut premium = state.start_premium;
+        for _ in 0..halvings {
+            if premium == 0 {
+                break;
+            }
+            premium /= 2u32;
+        }
+
+        // Interpolating linearly towards the next halving
+        let decay = &premium * remainder / (2 * state.premium_half_life);
+        premium - decay
+    }
+
+    // storage
+
+    #[storage_mapper("releasedAt")]
+    fn released_at(&self, domain: &ManagedBuffer) -> SingleValueMapper<u64>;
+}
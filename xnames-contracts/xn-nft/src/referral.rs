@@ -0,0 +1,135 @@
+use super::*;
+
+const COMMISSION_DENOMINATOR: u32 = 10_000;
+
+#[elrond_wasm::module]
+pub trait Referral: authority::Authority {
+    /// Function to manage the partner wallets earning a commission on the
+    /// registrations and renewals they refer, and their commission rates.
+    ///
+    ///  It rejects if:
+    ///  - If caller is not one of the admins.
+    ///  - The rate is more than 10_000 (100%).
+    #[endpoint(updateReferrer)]
+    fn update_referrer(&self, update_params: ReferrerUpdateParams<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            self.has_admin_rights(&caller),
+            "Unauthorized admin rights by the caller address!"
+        );
+
+        match update_params.kind {
+            UpdateKind::Remove => {
+                self.referral_rates().remove(&update_params.referrer);
+            }
+            UpdateKind::Add => {
+                require!(
+                    update_params.rate <= COMMISSION_DENOMINATOR,
+                    "Error: referral rate exceeds 100%!"
+                );
+                self.referral_rates()
+                    .insert(update_params.referrer, update_params.rate);
+            }
+        }
+    }
+
+    /// Function to send the commissions accumulated by the caller in a token
+    /// to it.
+    ///
+    /// It rejects if:
+    /// - There are no commissions to claim.
+    #[endpoint(claimReferralRewards)]
+    fn claim_referral_rewards(&self, token: EgldOrEsdtTokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+
+        let amount = self.referral_balance(&caller, &token).take();
+        require!(amount > 0, "Error: no referral rewards to claim!");
+
+        self.send().direct(&caller, &token, 0, &amount);
+
+        // Logging event
+        self.claim_referral_rewards_event(&caller, &token, &amount);
+    }
+
+    /// View the commission rate of a referrer, if it is one.
+    #[view(getReferralRate)]
+    fn get_referral_rate(&self, referrer: ManagedAddress) -> OptionalValue<u32> {
+        self.referral_rates().get(&referrer).into()
+    }
+
+    /// View the commissions accumulated by a referrer in a token.
+    #[view(getReferralBalance)]
+    fn get_referral_balance(
+        &self,
+        referrer: ManagedAddress,
+        token: EgldOrEsdtTokenIdentifier,
+    ) -> BigUint {
+        self.referral_balance(&referrer, &token).get()
+    }
+
+    // private
+
+    /// Credits the commission of the referrer on a payment and returns the
+    /// rest of it. Payments referred by an unknown referrer earn nothing.
+    fn pay_referral(
+        &self,
+        opt_referrer: &Option<ManagedAddress>,
+        payer: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) -> BigUint {
+        let referrer = match opt_referrer {
+            Some(referrer) => referrer,
+            None => return amount.clone(),
+        };
+        require!(referrer != payer, "Error: cannot refer yourself!");
+
+        let rate = match self.referral_rates().get(referrer) {
+            Some(rate) => rate,
+            None => return amount.clone(),
+        };
+
+        let commission = amount * rate / COMMISSION_DENOMINATOR;
+        if commission > 0 {
+            self.referral_balance(referrer, token)
+                .update(|balance| *balance += &commission);
+
+            // Logging event
+            self.referral_event(referrer, payer, token, &commission);
+        }
+
+        amount - &commission
+    }
+
+    // storage
+
+    #[storage_mapper("referralRates")]
+    fn referral_rates(&self) -> MapMapper<ManagedAddress, u32>;
+
+    #[storage_mapper("referralBalance")]
+    fn referral_balance(
+        &self,
+        referrer: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    // events
+
+    #[event("referral")]
+    fn referral_event(
+        &self,
+        #[indexed] referrer: &ManagedAddress,
+        #[indexed] payer: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        commission: &BigUint,
+    );
+
+    #[event("claimReferralRewards")]
+    fn claim_referral_rewards_event(
+        &self,
+        #[indexed] referrer: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+}
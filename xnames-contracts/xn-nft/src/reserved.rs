@@ -0,0 +1,53 @@
+use super::*;
+
+#[elrond_wasm::module]
+pub trait ReservedNames: validation::Validation + authority::Authority {
+    /// Function to manage reserved and blocked domains. Reserved domains can
+    /// only be assigned by admins, blocked domains cannot be registered at all.
+    ///
+    ///  It rejects if:
+    ///  - If caller is not one of the admins.
+    #[endpoint(updateNameRestriction)]
+    fn update_name_restriction(&self, update_params: NameRestrictionUpdateParams<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            self.has_admin_rights(&caller),
+            "Unauthorized admin rights by the caller address!"
+        );
+
+        let domain = self.normalize_domain(&update_params.domain);
+        match update_params.kind {
+            UpdateKind::Remove => {
+                self.name_restrictions().remove(&domain);
+            }
+            UpdateKind::Add => {
+                self.name_restrictions()
+                    .insert(domain, update_params.restriction);
+            }
+        }
+    }
+
+    /// Function to get the restriction of a domain, if any.
+    #[view(getNameRestriction)]
+    fn get_name_restriction(&self, domain: ManagedBuffer) -> OptionalValue<NameRestriction> {
+        self.name_restrictions()
+            .get(&self.normalize_domain(&domain))
+            .into()
+    }
+
+    // private
+
+    fn require_unrestricted(&self, domain: &ManagedBuffer) {
+        match self.name_restrictions().get(domain) {
+            Some(NameRestriction::Reserved) => sc_panic!("Error: domain is reserved!"),
+            Some(NameRestriction::Blocked) => sc_panic!("Error: domain is blocked!"),
+            None => (),
+        }
+    }
+
+    // storage
+
+    #[storage_mapper("nameRestrictions")]
+    fn name_restrictions(&self) -> MapMapper<ManagedBuffer, NameRestriction>;
+}
@@ -0,0 +1,354 @@
+use super::*;
+
+const MAX_TEXT_KEY_LENGTH: usize = 32;
+const MAX_TEXT_VALUE_LENGTH: usize = 512;
+// Caps keeping the records of a domain clearable within a transaction
+const MAX_COIN_ADDRESS_RECORDS: usize = 32;
+const MAX_TEXT_RECORDS: usize = 32;
+
+#[elrond_wasm::module]
+pub trait Resolver: storage::Storage + validation::Validation {
+    /// Function to set the address a domain resolves to. The primary name
+    /// claimed on the domain by the address it resolved to before is
+    /// released, unless that address owns the domain.
+    ///
+    /// It rejects if:
+    /// - The domain does not exist.
+    /// - The caller is not the owner of the domain.
+    #[endpoint(setAddressRecord)]
+    fn set_address_record(&self, domain: ManagedBuffer, address: ManagedAddress) {
+        let token_data = self.require_domain_owner(&domain);
+        let domain = token_data.domain;
+
+        // Releasing the primary name of the previously resolved address
+        let address_record = self.address_record(&domain);
+        if !address_record.is_empty() {
+            let previous_address = address_record.get();
+            if previous_address != address && previous_address != token_data.owner {
+                self.release_primary_name(&domain, &previous_address);
+            }
+        }
+
+        address_record.set(&address);
+
+        // Logging event
+        self.address_record_event(&domain, &address);
+    }
+
+    /// Function to resolve a domain to the address it points to. Domains
+    /// expired past their grace period do not resolve.
+    #[view(resolve)]
+    fn resolve(&self, domain: ManagedBuffer) -> OptionalValue<ManagedAddress> {
+        let domain = self.normalize_domain(&domain);
+        let mapper = self.address_record(&domain);
+
+        if mapper.is_empty() || !self.is_registered(&domain) {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(mapper.get())
+        }
+    }
+
+    /// Function to set the address of a domain on another chain, keyed by its
+    /// SLIP-44 coin type.
+    ///
+    /// It rejects if:
+    /// - The domain does not exist.
+    /// - The caller is not the owner of the domain.
+    /// - The address is not valid for the coin type.
+    /// - The domain already has 32 coin address records.
+    #[endpoint(setCoinAddressRecord)]
+    fn set_coin_address_record(
+        &self,
+        domain: ManagedBuffer,
+        coin_type: u32,
+        address: ManagedBuffer,
+    ) {
+        let domain = self.require_domain_owner(&domain).domain;
+
+        self.require_valid_coin_address(coin_type, &address);
+
+        let mut coin_address_records = self.coin_address_records(&domain);
+        require!(
+            coin_address_records.contains_key(&coin_type)
+                || coin_address_records.len() < MAX_COIN_ADDRESS_RECORDS,
+            "Error: too many coin address records!"
+        );
+        coin_address_records.insert(coin_type, address.clone());
+
+        // Logging event
+        self.coin_address_record_event(&domain, coin_type, &address);
+    }
+
+    /// Function to remove the address of a domain on another chain.
+    ///
+    /// It rejects if:
+    /// - The domain does not exist.
+    /// - The caller is not the owner of the domain.
+    /// - The coin address record does not exist.
+    #[endpoint(removeCoinAddressRecord)]
+    fn remove_coin_address_record(&self, domain: ManagedBuffer, coin_type: u32) {
+        let domain = self.require_domain_owner(&domain).domain;
+
+        require!(
+            self.coin_address_records(&domain)
+                .remove(&coin_type)
+                .is_some(),
+            "Error: coin address record does not exist!"
+        );
+
+        // Logging event
+        self.coin_address_record_event(&domain, coin_type, &ManagedBuffer::new());
+    }
+
+    /// Function to get the address of a domain on another chain. Domains
+    /// expired past their grace period have no addresses.
+    #[view(getCoinAddress)]
+    fn get_coin_address(
+        &self,
+        domain: ManagedBuffer,
+        coin_type: u32,
+    ) -> OptionalValue<ManagedBuffer> {
+        let domain = self.normalize_domain(&domain);
+        if !self.is_registered(&domain) {
+            return OptionalValue::None;
+        }
+
+        self.coin_address_records(&domain).get(&coin_type).into()
+    }
+
+    /// Function to get the addresses of a domain on all other chains.
+    /// Domains expired past their grace period have no addresses.
+    #[view(getCoinAddresses)]
+    fn get_coin_addresses(
+        &self,
+        domain: ManagedBuffer,
+    ) -> MultiValueEncoded<MultiValue2<u32, ManagedBuffer>> {
+        let domain = self.normalize_domain(&domain);
+        let mut result = MultiValueEncoded::new();
+        if !self.is_registered(&domain) {
+            return result;
+        }
+
+        for (coin_type, address) in self.coin_address_records(&domain).iter() {
+            result.push((coin_type, address).into());
+        }
+
+        result
+    }
+
+    /// Function to set a text record of a domain, such as `avatar`, `url` or
+    /// `twitter`.
+    ///
+    /// It rejects if:
+    /// - The domain does not exist.
+    /// - The caller is not the owner of the domain.
+    /// - The key is empty or longer than 32 bytes.
+    /// - The value is longer than 512 bytes.
+    /// - The domain already has 32 text records.
+    #[endpoint(setTextRecord)]
+    fn set_text_record(&self, domain: ManagedBuffer, key: ManagedBuffer, value: ManagedBuffer) {
+        let domain = self.require_domain_owner(&domain).domain;
+
+        require!(!key.is_empty(), "Error: text record key must not be empty!");
+        require!(
+            key.len() <= MAX_TEXT_KEY_LENGTH,
+            "Error: text record key is longer than 32 bytes!"
+        );
+        require!(
+            value.len() <= MAX_TEXT_VALUE_LENGTH,
+            "Error: text record value is longer than 512 bytes!"
+        );
+
+        let mut text_records = self.text_records(&domain);
+        require!(
+            text_records.contains_key(&key) || text_records.len() < MAX_TEXT_RECORDS,
+            "Error: too many text records!"
+        );
+        text_records.insert(key.clone(), value.clone());
+
+        // Logging event
+        self.text_record_event(&domain, &key, &value);
+    }
+
+    /// Function to remove a text record of a domain.
+    ///
+    /// It rejects if:
+    /// - The domain does not exist.
+    /// - The caller is not the owner of the domain.
+    /// - The text record does not exist.
+    #[endpoint(removeTextRecord)]
+    fn remove_text_record(&self, domain: ManagedBuffer, key: ManagedBuffer) {
+        let domain = self.require_domain_owner(&domain).domain;
+
+        require!(
+            self.text_records(&domain).remove(&key).is_some(),
+            "Error: text record does not exist!"
+        );
+
+        // Logging event
+        self.text_record_event(&domain, &key, &ManagedBuffer::new());
+    }
+
+    /// Function to get text records of a domain by keys. Missing records, and
+    /// all records of domains expired past their grace period, are returned
+    /// as empty values.
+    #[view(getTextRecords)]
+    fn get_text_records(
+        &self,
+        domain: ManagedBuffer,
+        keys: MultiValueEncoded<ManagedBuffer>,
+    ) -> MultiValueEncoded<MultiValue2<ManagedBuffer, ManagedBuffer>> {
+        let domain = self.normalize_domain(&domain);
+        let registered = self.is_registered(&domain);
+        let text_records = self.text_records(&domain);
+        let mut result = MultiValueEncoded::new();
+
+        for key in keys {
+            let value = if registered {
+                text_records.get(&key).unwrap_or_default()
+            } else {
+                ManagedBuffer::new()
+            };
+            result.push((key, value).into());
+        }
+
+        result
+    }
+
+    /// Function to claim an owned or resolving domain as the caller's primary
+    /// name, which is used for reverse resolution.
+    ///
+    /// It rejects if:
+    /// - The domain does not exist.
+    /// - The caller neither owns the domain nor is the address it resolves to.
+    #[endpoint(setPrimaryName)]
+    fn set_primary_name(&self, domain: ManagedBuffer) {
+        let caller = self.blockchain().get_caller();
+
+        let token_data = self.domain_details(&self.normalize_domain(&domain));
+
+        let domain = token_data.domain;
+        let address_record = self.address_record(&domain);
+        require!(
+            token_data.owner.eq(&caller)
+                || (!address_record.is_empty() && address_record.get().eq(&caller)),
+            "Error: caller must be the owner of the domain or its resolved address!"
+        );
+
+        // Releasing the previous claim
+        let primary_name = self.primary_name(&caller);
+        if !primary_name.is_empty() {
+            self.primary_name_claimants(&primary_name.get())
+                .swap_remove(&caller);
+        }
+
+        primary_name.set(&domain);
+        self.primary_name_claimants(&domain).insert(caller.clone());
+
+        // Logging event
+        self.primary_name_event(&caller, &domain);
+    }
+
+    /// Function to get the primary name of an address. Primary names of
+    /// domains expired past their grace period are not returned.
+    #[view(getPrimaryName)]
+    fn get_primary_name(&self, address: ManagedAddress) -> OptionalValue<ManagedBuffer> {
+        let mapper = self.primary_name(&address);
+        if mapper.is_empty() {
+            return OptionalValue::None;
+        }
+
+        let domain = mapper.get();
+        if self.is_registered(&domain) {
+            OptionalValue::Some(domain)
+        } else {
+            OptionalValue::None
+        }
+    }
+
+    // private
+
+    fn release_primary_name(&self, domain: &ManagedBuffer, address: &ManagedAddress) {
+        if self.primary_name_claimants(domain).swap_remove(address) {
+            self.primary_name(address).clear();
+        }
+    }
+
+    fn clear_primary_names(&self, domain: &ManagedBuffer) {
+        let mut claimants = self.primary_name_claimants(domain);
+
+        for address in claimants.iter() {
+            self.primary_name(&address).clear();
+        }
+        claimants.clear();
+    }
+
+    fn require_domain_owner(&self, domain: &ManagedBuffer) -> TokenData<Self::Api> {
+        let caller = self.blockchain().get_caller();
+
+        let token_data = self.domain_details(&self.normalize_domain(domain));
+
+        require!(
+            token_data.owner.eq(&caller),
+            "Error: caller must be the owner of the domain!"
+        );
+
+        token_data
+    }
+
+    fn clear_records(&self, domain: &ManagedBuffer) {
+        self.address_record(domain).clear();
+        self.coin_address_records(domain).clear();
+        self.text_records(domain).clear();
+    }
+
+    // storage
+
+    #[storage_mapper("addressRecord")]
+    fn address_record(&self, domain: &ManagedBuffer) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("coinAddressRecords")]
+    fn coin_address_records(&self, domain: &ManagedBuffer) -> MapMapper<u32, ManagedBuffer>;
+
+    #[storage_mapper("textRecords")]
+    fn text_records(&self, domain: &ManagedBuffer) -> MapMapper<ManagedBuffer, ManagedBuffer>;
+
+    #[storage_mapper("primaryName")]
+    fn primary_name(&self, address: &ManagedAddress) -> SingleValueMapper<ManagedBuffer>;
+
+    #[storage_mapper("primaryNameClaimants")]
+    fn primary_name_claimants(&self, domain: &ManagedBuffer) -> UnorderedSetMapper<ManagedAddress>;
+
+    // events
+
+    #[event("setAddressRecord")]
+    fn address_record_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] address: &ManagedAddress,
+    );
+
+    #[event("setCoinAddressRecord")]
+    fn coin_address_record_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] coin_type: u32,
+        address: &ManagedBuffer,
+    );
+
+    #[event("setTextRecord")]
+    fn text_record_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] key: &ManagedBuffer,
+        value: &ManagedBuffer,
+    );
+
+    #[event("setPrimaryName")]
+    fn primary_name_event(
+        &self,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] domain: &ManagedBuffer,
+    );
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+#[elrond_wasm::module]
+pub trait Storage {
+    /// Returns whether the domain is registered and not expired past its
+    /// grace period.
+    fn is_registered(&self, domain: &ManagedBuffer) -> bool {
+        let slot_time = self.blockchain().get_block_timestamp();
+        match self.find_domain_details(domain) {
+            Some(token_data) => !matches!(
+                SubscriptionData {
+                    owner: token_data.owner,
+                    expiry: token_data.expiry,
+                    grace: token_data.grace,
+                }
+                .into_status(slot_time)
+                .status,
+                SubscriptionExpiryStatus::Expired
+            ),
+            None => false,
+        }
+    }
+
+    /// Returns the details of a domain, given its normalized name.
+    fn domain_details(&self, domain: &ManagedBuffer) -> TokenData<Self::Api> {
+        self.find_domain_details(domain)
+            .expect("Error: domain does not exist!")
+    }
+
+    /// Returns the details of a domain, given its normalized name, if it
+    /// exists.
+    fn find_domain_details(&self, domain: &ManagedBuffer) -> Option<TokenData<Self::Api>> {
+        self.token_details().get(domain)
+    }
+
+    // storage
+
+    #[view]
+    #[storage_mapper("state")]
+    fn state(&self) -> SingleValueMapper<State<Self::Api>>;
+
+    #[view]
+    #[storage_mapper("tokenDetails")]
+    fn token_details(&self) -> MapMapper<ManagedBuffer, TokenData<Self::Api>>;
+
+    #[storage_mapper("nftDomain")]
+    fn nft_domain(
+        &self,
+        token_id: &TokenIdentifier,
+        nft_nonce: u64,
+    ) -> SingleValueMapper<ManagedBuffer>;
+}
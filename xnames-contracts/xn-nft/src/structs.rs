@@ -5,6 +5,12 @@ pub struct State<M: ManagedTypeApi> {
     pub grace: u64,
     pub benificiary: ManagedAddress<M>,
     pub royalty: BigUint<M>,
+    pub price_oracle: ManagedAddress<M>,
+    pub min_commitment_age: u64,
+    pub max_commitment_age: u64,
+    pub start_premium: BigUint<M>,
+    pub premium_half_life: u64,
+    pub premium_duration: u64,
 }
 
 impl<M: ManagedTypeApi> State<M> {
@@ -12,6 +18,12 @@ impl<M: ManagedTypeApi> State<M> {
         self.grace = state.grace;
         self.benificiary = state.benificiary;
         self.royalty = state.royalty;
+        self.price_oracle = state.price_oracle;
+        self.min_commitment_age = state.min_commitment_age;
+        self.max_commitment_age = state.max_commitment_age;
+        self.start_premium = state.start_premium;
+        self.premium_half_life = state.premium_half_life;
+        self.premium_duration = state.premium_duration;
     }
 }
 
@@ -34,10 +46,45 @@ pub struct AuthorityUpdateParams<M: ManagedTypeApi> {
     pub address: ManagedAddress<M>,
 }
 
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct FeeSplitUpdateParams<M: ManagedTypeApi> {
+    pub kind: UpdateKind,
+    pub recipient: ManagedAddress<M>,
+    /// Share of the fees, between 0 (0%) and 10_000 (100%).
+    pub share: u32,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct ReferrerUpdateParams<M: ManagedTypeApi> {
+    pub kind: UpdateKind,
+    pub referrer: ManagedAddress<M>,
+    /// Commission on referred payments, between 0 (0%) and 10_000 (100%).
+    pub rate: u32,
+}
+
 #[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
 pub enum UpdateInternalValueParams<M: ManagedTypeApi> {
     Royalty(BigUint<M>),
     Beneficiary(ManagedAddress<M>),
+    PriceOracle(ManagedAddress<M>),
+    MinCommitmentAge(u64),
+    MaxCommitmentAge(u64),
+    StartPremium(BigUint<M>),
+    PremiumHalfLife(u64),
+    PremiumDuration(u64),
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub enum NameRestriction {
+    Reserved,
+    Blocked,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct NameRestrictionUpdateParams<M: ManagedTypeApi> {
+    pub restriction: NameRestriction,
+    pub kind: UpdateKind,
+    pub domain: ManagedBuffer<M>,
 }
 
 /// Minting Data.
@@ -66,6 +113,10 @@ pub struct TokenData<M: ManagedTypeApi> {
     pub domain: ManagedBuffer<M>,
     /// Royalty.
     pub royalty: BigUint<M>,
+    /// TokenId the domain NFT is minted with.
+    pub token_id: TokenIdentifier<M>,
+    /// The nonce of the domain NFT.
+    pub nft_nonce: u64,
 }
 
 impl<M: ManagedTypeApi> TokenData<M> {
@@ -75,6 +126,7 @@ impl<M: ManagedTypeApi> TokenData<M> {
         grace: u64,
         domain: ManagedBuffer<M>,
         royalty: BigUint<M>,
+        token_id: TokenIdentifier<M>,
     ) -> Self {
         Self {
             owner,
@@ -82,6 +134,8 @@ impl<M: ManagedTypeApi> TokenData<M> {
             grace,
             domain,
             royalty,
+            token_id,
+            nft_nonce: 0,
         }
     }
 }
@@ -89,8 +143,8 @@ impl<M: ManagedTypeApi> TokenData<M> {
 /// Transfer Data.
 #[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
 pub struct Transfer<M: ManagedTypeApi> {
-    /// The ID of the token being transferred.
-    pub token_id: TokenIdentifier<M>,
+    /// The domain being transferred.
+    pub domain: ManagedBuffer<M>,
     /// The amount of tokens being transferred.
     pub amount: u64,
     /// The address owning the tokens being transferred.
@@ -147,9 +201,57 @@ pub struct TokenSubscriptionStatus<M: ManagedTypeApi> {
     pub status: SubscriptionExpiryStatus,
 }
 
+// Domain Availability
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub enum DomainAvailability {
+    Available,
+    Registered,
+    Auctioned,
+    Reserved,
+    Blocked,
+}
+
 // Token Info
 #[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
 pub struct TokenInfo<M: ManagedTypeApi> {
     pub domain: ManagedBuffer<M>,
     pub royalty: BigUint<M>,
+    pub token_id: TokenIdentifier<M>,
+    pub nft_nonce: u64,
+}
+
+/// Subdomain Data
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct SubdomainData<M: ManagedTypeApi> {
+    /// The account address of owner.
+    pub owner: ManagedAddress<M>,
+    /// The expiry timestamp, capped by the parent domain expiry.
+    pub expiry: u64,
+}
+
+// Subdomain Info
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct SubdomainInfo<M: ManagedTypeApi> {
+    pub label: ManagedBuffer<M>,
+    pub owner: ManagedAddress<M>,
+    pub expiry: u64,
+}
+
+/// Auction Data
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct Auction<M: ManagedTypeApi> {
+    /// TokenId the domain is minted with.
+    pub token_id: TokenIdentifier<M>,
+    /// Subscription duration granted to the winner.
+    pub duration: u64,
+    /// Token accepted for bids.
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    /// The minimum accepted bid.
+    pub min_bid: BigUint<M>,
+    /// The end timestamp.
+    pub end: u64,
+    /// The account address of the highest bidder.
+    pub highest_bidder: ManagedAddress<M>,
+    /// The highest bid, zero while there are no bids.
+    pub highest_bid: BigUint<M>,
 }
@@ -0,0 +1,278 @@
+use super::*;
+
+// Cap keeping the subdomains of a domain clearable within a transaction
+const MAX_SUBDOMAINS: usize = 32;
+
+#[elrond_wasm::module]
+pub trait Subdomain: storage::Storage + validation::Validation {
+    /// Function to create a subdomain `label` under an owned domain. The
+    /// subdomain expiry is capped by the parent domain expiry.
+    ///
+    /// It rejects if:
+    /// - The domain does not exist.
+    /// - The caller is not the owner of the parent domain.
+    /// - The parent domain subscription is not active.
+    /// - The expiry is not in the future.
+    /// - The label is not valid or is already taken by an active subdomain.
+    /// - The domain already has 32 subdomains.
+    #[endpoint(createSubdomain)]
+    fn create_subdomain(
+        &self,
+        domain: ManagedBuffer,
+        label: ManagedBuffer,
+        owner: ManagedAddress,
+        expiry: u64,
+    ) {
+        let slot_time = self.blockchain().get_block_timestamp();
+        let token_data = self.require_parent_owner(&domain);
+
+        require!(
+            token_data.expiry >= slot_time,
+            "Error: parent domain subscription is not active!"
+        );
+        require!(
+            expiry > slot_time,
+            "Error: subdomain expiry must be in the future!"
+        );
+
+        let label = self.normalize_domain(&label);
+        let mut subdomains = self.subdomains(&token_data.domain);
+        if let Some(subdomain) = subdomains.get(&label) {
+            require!(
+                subdomain.expiry < slot_time,
+                "Error: subdomain already exists!"
+            );
+        } else {
+            require!(
+                subdomains.len() < MAX_SUBDOMAINS,
+                "Error: too many subdomains!"
+            );
+        }
+        self.subdomain_address_record(&token_data.domain, &label)
+            .clear();
+
+        let expiry = expiry.min(token_data.expiry);
+        subdomains.insert(
+            label.clone(),
+            SubdomainData {
+                owner: owner.clone(),
+                expiry,
+            },
+        );
+
+        // Logging event
+        self.create_subdomain_event(&token_data.domain, &label, &owner, &expiry);
+    }
+
+    /// Function to assign an existing subdomain to a new owner, clearing its
+    /// address record.
+    ///
+    /// It rejects if:
+    /// - The domain does not exist.
+    /// - The caller is not the owner of the parent domain.
+    /// - The subdomain does not exist.
+    #[endpoint(reassignSubdomain)]
+    fn reassign_subdomain(
+        &self,
+        domain: ManagedBuffer,
+        label: ManagedBuffer,
+        owner: ManagedAddress,
+    ) {
+        let token_data = self.require_parent_owner(&domain);
+
+        let label = self.normalize_domain(&label);
+        let mut subdomains = self.subdomains(&token_data.domain);
+        require!(
+            subdomains.contains_key(&label),
+            "Error: subdomain does not exist!"
+        );
+
+        subdomains
+            .entry(label.clone())
+            .and_modify(|subdomain| subdomain.owner = owner.clone());
+        self.subdomain_address_record(&token_data.domain, &label)
+            .clear();
+
+        // Logging event
+        self.reassign_subdomain_event(&token_data.domain, &label, &owner);
+    }
+
+    /// Function to revoke a subdomain.
+    ///
+    /// It rejects if:
+    /// - The domain does not exist.
+    /// - The caller is not the owner of the parent domain.
+    /// - The subdomain does not exist.
+    #[endpoint(revokeSubdomain)]
+    fn revoke_subdomain(&self, domain: ManagedBuffer, label: ManagedBuffer) {
+        let token_data = self.require_parent_owner(&domain);
+
+        let label = self.normalize_domain(&label);
+        let subdomain = self
+            .subdomains(&token_data.domain)
+            .remove(&label)
+            .expect("Error: subdomain does not exist!");
+        self.subdomain_address_record(&token_data.domain, &label)
+            .clear();
+
+        // Logging event
+        self.revoke_subdomain_event(&token_data.domain, &label, &subdomain.owner);
+    }
+
+    /// Function to set the address a subdomain resolves to.
+    ///
+    /// It rejects if:
+    /// - The domain does not exist.
+    /// - The subdomain does not exist.
+    /// - The caller is not the owner of the subdomain.
+    /// - The subdomain is expired.
+    #[endpoint(setSubdomainAddressRecord)]
+    fn set_subdomain_address_record(
+        &self,
+        domain: ManagedBuffer,
+        label: ManagedBuffer,
+        address: ManagedAddress,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let slot_time = self.blockchain().get_block_timestamp();
+        let domain = self.domain_details(&self.normalize_domain(&domain)).domain;
+
+        let label = self.normalize_domain(&label);
+        let subdomain = self
+            .subdomains(&domain)
+            .get(&label)
+            .expect("Error: subdomain does not exist!");
+
+        require!(
+            subdomain.owner == caller,
+            "Error: caller must be the owner of the subdomain!"
+        );
+        require!(
+            subdomain.expiry >= slot_time,
+            "Error: subdomain is expired!"
+        );
+
+        self.subdomain_address_record(&domain, &label).set(&address);
+
+        // Logging event
+        self.subdomain_address_record_event(&domain, &label, &address);
+    }
+
+    /// Function to resolve a subdomain to the address it points to. Expired
+    /// subdomains, and subdomains of domains expired past their grace
+    /// period, do not resolve.
+    #[view(resolveSubdomain)]
+    fn resolve_subdomain(
+        &self,
+        domain: ManagedBuffer,
+        label: ManagedBuffer,
+    ) -> OptionalValue<ManagedAddress> {
+        let slot_time = self.blockchain().get_block_timestamp();
+        let domain = self.normalize_domain(&domain);
+        let label = self.normalize_domain(&label);
+
+        if !self.is_registered(&domain) {
+            return OptionalValue::None;
+        }
+        match self.subdomains(&domain).get(&label) {
+            Some(subdomain) if subdomain.expiry >= slot_time => {}
+            _ => return OptionalValue::None,
+        }
+
+        let mapper = self.subdomain_address_record(&domain, &label);
+        if mapper.is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(mapper.get())
+        }
+    }
+
+    /// Function to list the subdomains of a domain.
+    #[view(getSubdomains)]
+    fn get_subdomains(&self, domain: ManagedBuffer) -> MultiValueEncoded<SubdomainInfo<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+
+        for (label, subdomain) in self.subdomains(&self.normalize_domain(&domain)).iter() {
+            result.push(SubdomainInfo {
+                label,
+                owner: subdomain.owner,
+                expiry: subdomain.expiry,
+            });
+        }
+
+        result
+    }
+
+    // private
+
+    fn require_parent_owner(&self, domain: &ManagedBuffer) -> TokenData<Self::Api> {
+        let caller = self.blockchain().get_caller();
+
+        let token_data = self.domain_details(&self.normalize_domain(domain));
+
+        require!(
+            token_data.owner.eq(&caller),
+            "Error: caller must be the owner of the parent domain!"
+        );
+
+        token_data
+    }
+
+    fn clear_subdomains(&self, domain: &ManagedBuffer) {
+        let mut subdomains = self.subdomains(domain);
+        for label in subdomains.keys() {
+            self.subdomain_address_record(domain, &label).clear();
+        }
+        subdomains.clear();
+    }
+
+    // storage
+
+    #[storage_mapper("subdomains")]
+    fn subdomains(
+        &self,
+        domain: &ManagedBuffer,
+    ) -> MapMapper<ManagedBuffer, SubdomainData<Self::Api>>;
+
+    #[storage_mapper("subdomainAddressRecord")]
+    fn subdomain_address_record(
+        &self,
+        domain: &ManagedBuffer,
+        label: &ManagedBuffer,
+    ) -> SingleValueMapper<ManagedAddress>;
+
+    // events
+
+    #[event("createSubdomain")]
+    fn create_subdomain_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] label: &ManagedBuffer,
+        #[indexed] owner: &ManagedAddress,
+        expiry: &u64,
+    );
+
+    #[event("reassignSubdomain")]
+    fn reassign_subdomain_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] label: &ManagedBuffer,
+        #[indexed] owner: &ManagedAddress,
+    );
+
+    #[event("subdomainAddressRecord")]
+    fn subdomain_address_record_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] label: &ManagedBuffer,
+        #[indexed] address: &ManagedAddress,
+    );
+
+    #[event("revokeSubdomain")]
+    fn revoke_subdomain_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] label: &ManagedBuffer,
+        #[indexed] owner: &ManagedAddress,
+    );
+}
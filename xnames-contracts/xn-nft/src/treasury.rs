@@ -0,0 +1,132 @@
+use super::*;
+
+const SPLIT_DENOMINATOR: u32 = 10_000;
+
+#[elrond_wasm::module]
+pub trait Treasury: storage::Storage + authority::Authority {
+    /// Function to manage the recipients that registration and renewal fees
+    /// are split between, such as the treasury or a burn address. The share
+    /// not assigned to any recipient goes to the beneficiary.
+    ///
+    ///  It rejects if:
+    ///  - If caller is not one of the admins.
+    ///  - The share of the recipient is more than 10_000 (100%).
+    ///  - The shares of all recipients add up to more than 10_000 (100%).
+    #[endpoint(updateFeeSplit)]
+    fn update_fee_split(&self, update_params: FeeSplitUpdateParams<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            self.has_admin_rights(&caller),
+            "Unauthorized admin rights by the caller address!"
+        );
+
+        match update_params.kind {
+            UpdateKind::Remove => {
+                self.fee_splits().remove(&update_params.recipient);
+            }
+            UpdateKind::Add => {
+                require!(
+                    update_params.share <= SPLIT_DENOMINATOR,
+                    "Error: fee split share exceeds 100%!"
+                );
+                self.fee_splits()
+                    .insert(update_params.recipient, update_params.share);
+            }
+        }
+
+        let total_share: u64 = self.fee_splits().values().map(u64::from).sum();
+        require!(
+            total_share <= u64::from(SPLIT_DENOMINATOR),
+            "Error: fee split shares exceed 100%!"
+        );
+    }
+
+    /// Function to send the fees accumulated by a recipient in a token to it.
+    ///
+    /// It rejects if:
+    /// - There are no fees to withdraw.
+    #[endpoint(withdraw)]
+    fn withdraw(&self, recipient: ManagedAddress, token: EgldOrEsdtTokenIdentifier) {
+        let amount = self.fee_balance(&recipient, &token).take();
+        require!(amount > 0, "Error: no fees to withdraw!");
+
+        self.send().direct(&recipient, &token, 0, &amount);
+
+        // Logging event
+        self.withdraw_event(&recipient, &token, &amount);
+    }
+
+    /// View the recipients of the fees and their shares.
+    #[view(getFeeSplits)]
+    fn get_fee_splits(&self) -> MultiValueEncoded<MultiValue2<ManagedAddress, u32>> {
+        let mut result = MultiValueEncoded::new();
+
+        for (recipient, share) in self.fee_splits().iter() {
+            result.push((recipient, share).into());
+        }
+
+        result
+    }
+
+    /// View the fees accumulated by a recipient in a token.
+    #[view(getFeeBalance)]
+    fn get_fee_balance(
+        &self,
+        recipient: ManagedAddress,
+        token: EgldOrEsdtTokenIdentifier,
+    ) -> BigUint {
+        self.fee_balance(&recipient, &token).get()
+    }
+
+    // private
+
+    /// Splits a fee payment between the recipients, the rest going to the
+    /// beneficiary.
+    fn deposit_fees(&self, token: &EgldOrEsdtTokenIdentifier, amount: &BigUint) {
+        let mut remainder = amount.clone();
+
+        for (recipient, share) in self.fee_splits().iter() {
+            let recipient_amount = amount * share / SPLIT_DENOMINATOR;
+            remainder -= &recipient_amount;
+            self.credit_fees(&recipient, token, &recipient_amount);
+        }
+
+        let beneficiary = self.state().get().benificiary;
+        self.credit_fees(&beneficiary, token, &remainder);
+    }
+
+    fn credit_fees(
+        &self,
+        recipient: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        if amount > &0 {
+            self.fee_balance(recipient, token)
+                .update(|balance| *balance += amount);
+        }
+    }
+
+    // storage
+
+    #[storage_mapper("feeSplits")]
+    fn fee_splits(&self) -> MapMapper<ManagedAddress, u32>;
+
+    #[storage_mapper("feeBalance")]
+    fn fee_balance(
+        &self,
+        recipient: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    // events
+
+    #[event("withdraw")]
+    fn withdraw_event(
+        &self,
+        #[indexed] recipient: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+}
@@ -0,0 +1,125 @@
+use super::*;
+use common::punycode::{punycode_length, PUNYCODE_PREFIX};
+
+const MAX_DOMAIN_LENGTH: usize = 63;
+
+const COIN_TYPE_BTC: u32 = 0;
+const COIN_TYPE_ETH: u32 = 60;
+const COIN_TYPE_EGLD: u32 = 508;
+const MAX_COIN_ADDRESS_LENGTH: usize = 128;
+
+#[elrond_wasm::module]
+pub trait Validation {
+    /// Function to get the normalized form of a domain, as it is stored and
+    /// looked up by the contract.
+    #[view(getNormalizedDomain)]
+    fn get_normalized_domain(&self, domain: ManagedBuffer) -> ManagedBuffer {
+        self.normalize_domain(&domain)
+    }
+
+    // private
+
+    /// Validates a domain label and lowercases its ASCII letters.
+    ///
+    /// It rejects if:
+    /// - The label is empty or longer than 63 bytes.
+    /// - The label contains bytes other than `a-z`, `0-9` and `-`. Unicode
+    ///   labels must be punycode encoded with the `xn--` prefix.
+    /// - The label starts or ends with a hyphen.
+    /// - The label has hyphens in the third and fourth positions without being
+    ///   a punycode label, or is a punycode label that does not decode
+    ///   following RFC 3492.
+    fn normalize_domain(&self, domain: &ManagedBuffer) -> ManagedBuffer {
+        let mut bytes = domain.to_boxed_bytes().into_vec();
+        bytes.make_ascii_lowercase();
+
+        require!(!bytes.is_empty(), "Error: domain must not be empty!");
+        require!(
+            bytes.len() <= MAX_DOMAIN_LENGTH,
+            "Error: domain is longer than 63 characters!"
+        );
+
+        for byte in bytes.iter() {
+            require!(
+                byte.is_ascii(),
+                "Error: domain must be ASCII, unicode labels must be punycode encoded!"
+            );
+            require!(
+                byte.is_ascii_lowercase() || byte.is_ascii_digit() || *byte == b'-',
+                "Error: domain may only contain letters, digits and hyphens!"
+            );
+        }
+
+        require!(
+            bytes[0] != b'-',
+            "Error: domain must not start with a hyphen!"
+        );
+        require!(
+            bytes[bytes.len() - 1] != b'-',
+            "Error: domain must not end with a hyphen!"
+        );
+
+        if let Some(encoded) = bytes.strip_prefix(PUNYCODE_PREFIX) {
+            require!(
+                punycode_length(encoded).is_some(),
+                "Error: domain is not valid punycode!"
+            );
+        } else {
+            require!(
+                bytes.len() < 4 || &bytes[2..4] != b"--",
+                "Error: hyphens in the third and fourth positions are reserved for punycode!"
+            );
+        }
+
+        ManagedBuffer::new_from_bytes(&bytes)
+    }
+
+    /// Validates the raw bytes of an address on another chain, keyed by its
+    /// SLIP-44 coin type. Bitcoin addresses are stored as their output
+    /// script, Ethereum addresses as 20 bytes and Elrond addresses as 32
+    /// bytes. Other coin types are only checked for length.
+    ///
+    /// It rejects if:
+    /// - The address is empty or longer than 128 bytes.
+    /// - The address does not match the format of a well-known coin type.
+    fn require_valid_coin_address(&self, coin_type: u32, address: &ManagedBuffer) {
+        require!(!address.is_empty(), "Error: address must not be empty!");
+        require!(
+            address.len() <= MAX_COIN_ADDRESS_LENGTH,
+            "Error: address is longer than 128 bytes!"
+        );
+
+        let bytes = address.to_boxed_bytes();
+        let bytes = bytes.as_slice();
+        match coin_type {
+            COIN_TYPE_BTC => require!(
+                is_btc_output_script(bytes),
+                "Error: invalid BTC address, expected a P2PKH, P2SH or segwit output script!"
+            ),
+            COIN_TYPE_ETH => require!(
+                bytes.len() == 20,
+                "Error: invalid ETH address, expected 20 bytes!"
+            ),
+            COIN_TYPE_EGLD => require!(
+                bytes.len() == 32,
+                "Error: invalid EGLD address, expected 32 bytes!"
+            ),
+            _ => (),
+        }
+    }
+}
+
+fn is_btc_output_script(script: &[u8]) -> bool {
+    match script {
+        // P2PKH: OP_DUP OP_HASH160 <20 bytes> OP_EQUALVERIFY OP_CHECKSIG
+        [0x76, 0xa9, 0x14, hash @ .., 0x88, 0xac] => hash.len() == 20,
+        // P2SH: OP_HASH160 <20 bytes> OP_EQUAL
+        [0xa9, 0x14, hash @ .., 0x87] => hash.len() == 20,
+        // Segwit v0: OP_0 <20 or 32 bytes>
+        [0x00, 0x14, program @ ..] => program.len() == 20,
+        [0x00, 0x20, program @ ..] => program.len() == 32,
+        // Segwit v1 (taproot): OP_1 <32 bytes>
+        [0x51, 0x20, program @ ..] => program.len() == 32,
+        _ => false,
+    }
+}
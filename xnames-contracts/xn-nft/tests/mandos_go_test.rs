@@ -12,3 +12,23 @@ fn create_nft_go() {
 fn buy_nft_go() {
     elrond_wasm_debug::mandos_go("mandos/buy_nft.scen.json");
 }
+
+#[test]
+fn validation_go() {
+    elrond_wasm_debug::mandos_go("mandos/validation.scen.json");
+}
+
+#[test]
+fn reserved_go() {
+    elrond_wasm_debug::mandos_go("mandos/reserved.scen.json");
+}
+
+#[test]
+fn referral_go() {
+    elrond_wasm_debug::mandos_go("mandos/referral.scen.json");
+}
+
+#[test]
+fn create_nft_admin_go() {
+    elrond_wasm_debug::mandos_go("mandos/create_nft_admin.scen.json");
+}
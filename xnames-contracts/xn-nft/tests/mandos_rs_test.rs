@@ -0,0 +1,28 @@
+use elrond_wasm_debug::*;
+
+fn world() -> BlockchainMock {
+    let mut blockchain = BlockchainMock::new();
+
+    blockchain.register_contract("file:output/xn-nft.wasm", xn_nft::ContractBuilder);
+    blockchain
+}
+
+#[test]
+fn validation_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/validation.scen.json", world());
+}
+
+#[test]
+fn reserved_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/reserved.scen.json", world());
+}
+
+#[test]
+fn referral_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/referral.scen.json", world());
+}
+
+#[test]
+fn create_nft_admin_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/create_nft_admin.scen.json", world());
+}
@@ -0,0 +1,2064 @@
+use std::panic::AssertUnwindSafe;
+
+use domain_receiver_mock::DomainReceiverMock;
+use elrond_wasm::{
+    elrond_codec::multi_types::OptionalValue,
+    types::{Address, EgldOrEsdtTokenIdentifier, EsdtLocalRole, ManagedVec, MultiValueEncoded},
+};
+use elrond_wasm_debug::{
+    managed_address, managed_biguint, managed_buffer, managed_token_id, rust_biguint,
+    testing_framework::*,
+    tx_mock::{TxPanic, TxResult, TxTokenTransfer},
+    DebugApi,
+};
+use price_oracle::{PriceOracleContract, PriceType};
+use xn_nft::{
+    approval::Approval, auction::AuctionModule, commitment::Commitment, nft_module::NftModule,
+    premium::Premium, referral::Referral, reserved::ReservedNames, resolver::Resolver,
+    storage::Storage, subdomain::Subdomain, treasury::Treasury, *,
+};
+
+const XN_NFT_WASM_PATH: &str = "output/xn-nft.wasm";
+const PRICE_ORACLE_WASM_PATH: &str = "../price_oracle/output/price_oracle.wasm";
+const DOMAIN_RECEIVER_MOCK_WASM_PATH: &str =
+    "../domain-receiver-mock/output/domain-receiver-mock.wasm";
+
+const NFT_TOKEN_ID: &[u8] = b"XN-123456";
+const YEAR: u64 = 31_536_000;
+const GRACE: u64 = 86_400;
+const MIN_COMMITMENT_AGE: u64 = 60;
+const START: u64 = 1_000_000;
+const EGLD: u64 = 1_000_000_000_000_000_000;
+
+struct ContractSetup<NftObjBuilder, OracleObjBuilder>
+where
+    NftObjBuilder: 'static + Copy + Fn() -> xn_nft::ContractObj<DebugApi>,
+    OracleObjBuilder: 'static + Copy + Fn() -> price_oracle::ContractObj<DebugApi>,
+{
+    pub blockchain_wrapper: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub beneficiary_address: Address,
+    pub user_address: Address,
+    pub nft_wrapper: ContractObjWrapper<xn_nft::ContractObj<DebugApi>, NftObjBuilder>,
+    pub oracle_wrapper: ContractObjWrapper<price_oracle::ContractObj<DebugApi>, OracleObjBuilder>,
+}
+
+fn setup_contract<NftObjBuilder, OracleObjBuilder>(
+    nft_builder: NftObjBuilder,
+    oracle_builder: OracleObjBuilder,
+) -> ContractSetup<NftObjBuilder, OracleObjBuilder>
+where
+    NftObjBuilder: 'static + Copy + Fn() -> xn_nft::ContractObj<DebugApi>,
+    OracleObjBuilder: 'static + Copy + Fn() -> price_oracle::ContractObj<DebugApi>,
+{
+    let rust_zero = rust_biguint!(0u64);
+    let mut blockchain_wrapper = BlockchainStateWrapper::new();
+    let owner_address = blockchain_wrapper.create_user_account(&rust_zero);
+    let beneficiary_address = blockchain_wrapper.create_user_account(&rust_zero);
+    let user_address = blockchain_wrapper.create_user_account(&rust_biguint!(1_000_000_000u64));
+
+    let oracle_wrapper = blockchain_wrapper.create_sc_account(
+        &rust_zero,
+        Some(&owner_address),
+        oracle_builder,
+        PRICE_ORACLE_WASM_PATH,
+    );
+    blockchain_wrapper
+        .execute_tx(&owner_address, &oracle_wrapper, &rust_zero, |sc| {
+            sc.init();
+        })
+        .assert_ok();
+
+    let nft_wrapper = blockchain_wrapper.create_sc_account(
+        &rust_zero,
+        Some(&owner_address),
+        nft_builder,
+        XN_NFT_WASM_PATH,
+    );
+    blockchain_wrapper.set_esdt_local_roles(
+        nft_wrapper.address_ref(),
+        NFT_TOKEN_ID,
+        &[EsdtLocalRole::NftCreate],
+    );
+
+    let oracle_address = oracle_wrapper.address_ref().clone();
+    blockchain_wrapper
+        .execute_tx(&owner_address, &nft_wrapper, &rust_zero, |sc| {
+            sc.init(State {
+                grace: GRACE,
+                benificiary: managed_address!(&beneficiary_address),
+                royalty: managed_biguint!(1_000u64),
+                price_oracle: managed_address!(&oracle_address),
+                min_commitment_age: MIN_COMMITMENT_AGE,
+                max_commitment_age: 86_400,
+                start_premium: managed_biguint!(100_000_000u64),
+                premium_half_life: 86_400,
+                premium_duration: 1_814_400,
+            });
+        })
+        .assert_ok();
+
+    blockchain_wrapper.set_block_timestamp(START);
+
+    ContractSetup {
+        blockchain_wrapper,
+        owner_address,
+        beneficiary_address,
+        user_address,
+        nft_wrapper,
+        oracle_wrapper,
+    }
+}
+
+fn mint_params(domain: &[u8], owner: &Address, duration: u64) -> MintParams<DebugApi> {
+    MintParams {
+        token_id: managed_token_id!(NFT_TOKEN_ID),
+        domain: managed_buffer!(domain),
+        owner: managed_address!(owner),
+        duration,
+    }
+}
+
+/// Returns a smart contract address next to `address`, told apart by `tag`,
+/// in the given shard.
+fn sc_address_in_shard(address: &Address, tag: u8, shard: u8) -> Address {
+    let mut bytes = *address.as_array();
+    bytes[30] ^= tag;
+    bytes[31] = shard;
+    Address::from(bytes)
+}
+
+fn shard_of(address: &Address) -> u8 {
+    let last_byte = address.as_array()[31];
+    if last_byte & 0b11 < 3 {
+        last_byte & 0b11
+    } else {
+        last_byte & 0b01
+    }
+}
+
+impl<NftObjBuilder, OracleObjBuilder> ContractSetup<NftObjBuilder, OracleObjBuilder>
+where
+    NftObjBuilder: 'static + Copy + Fn() -> xn_nft::ContractObj<DebugApi>,
+    OracleObjBuilder: 'static + Copy + Fn() -> price_oracle::ContractObj<DebugApi>,
+{
+    /// Registers `domain` for a year through commit and reveal, paying
+    /// `payment` EGLD, and returns the current timestamp afterwards.
+    fn register(&mut self, caller: &Address, domain: &[u8], payment: u64, now: u64) -> u64 {
+        let owner = caller.clone();
+        self.blockchain_wrapper.set_block_timestamp(now);
+        self.blockchain_wrapper
+            .execute_tx(caller, &self.nft_wrapper, &rust_biguint!(0u64), |sc| {
+                let hash = sc.get_commitment_hash(
+                    managed_buffer!(domain),
+                    managed_address!(&owner),
+                    managed_buffer!(b"secret"),
+                );
+                sc.commit(hash);
+            })
+            .assert_ok();
+
+        let now = now + MIN_COMMITMENT_AGE;
+        self.blockchain_wrapper.set_block_timestamp(now);
+        self.blockchain_wrapper
+            .execute_tx(caller, &self.nft_wrapper, &rust_biguint!(payment), |sc| {
+                sc.reveal(
+                    managed_buffer!(domain),
+                    managed_buffer!(b"www.xnames.io"),
+                    mint_params(domain, &owner, YEAR),
+                    managed_buffer!(b"secret"),
+                    OptionalValue::None,
+                );
+            })
+            .assert_ok();
+
+        now
+    }
+
+    /// Prices every domain at `price` EGLD a year.
+    fn set_price(&mut self, price: u64) {
+        self.blockchain_wrapper
+            .execute_tx(
+                &self.owner_address,
+                &self.oracle_wrapper,
+                &rust_biguint!(0u64),
+                |sc| {
+                    sc.set_price(
+                        PriceType::Fixed,
+                        managed_biguint!(price),
+                        ManagedVec::new(),
+                        managed_biguint!(0u64),
+                    );
+                },
+            )
+            .assert_ok();
+    }
+
+    /// Returns the nonce of the NFT a domain is bound to.
+    fn nft_nonce(&mut self, domain: &[u8]) -> u64 {
+        let mut nft_nonce = 0;
+        self.blockchain_wrapper
+            .execute_query(&self.nft_wrapper, |sc| {
+                nft_nonce = sc.domain_details(&managed_buffer!(domain)).nft_nonce;
+            })
+            .assert_ok();
+        nft_nonce
+    }
+
+    /// Transfers `domain` from `from` to `to`, sending the domain NFTs of
+    /// the given nonces along.
+    fn transfer(
+        &mut self,
+        caller: &Address,
+        nft_nonces: &[u64],
+        domain: &[u8],
+        amount: u64,
+        from: &Address,
+        to: &Address,
+    ) -> TxResult {
+        let payments: Vec<TxTokenTransfer> = nft_nonces
+            .iter()
+            .map(|nonce| TxTokenTransfer {
+                token_identifier: NFT_TOKEN_ID.to_vec(),
+                nonce: *nonce,
+                value: rust_biguint!(1u64),
+            })
+            .collect();
+
+        self.blockchain_wrapper.execute_esdt_multi_transfer(
+            caller,
+            &self.nft_wrapper,
+            &payments,
+            |sc| {
+                sc.transfer(vec![Transfer {
+                    domain: managed_buffer!(domain),
+                    amount,
+                    from: managed_address!(from),
+                    to: managed_address!(to),
+                    data: Vec::new(),
+                }]);
+            },
+        )
+    }
+
+    /// Moves the domain NFT of the given nonce between two accounts outside
+    /// of the contract.
+    fn move_nft(&mut self, from: &Address, to: &Address, nft_nonce: u64) {
+        let attributes: Vec<u8> = Vec::new();
+        self.blockchain_wrapper.set_nft_balance(
+            from,
+            NFT_TOKEN_ID,
+            nft_nonce,
+            &rust_biguint!(0u64),
+            &attributes,
+        );
+        self.blockchain_wrapper.set_nft_balance(
+            to,
+            NFT_TOKEN_ID,
+            nft_nonce,
+            &rust_biguint!(1u64),
+            &attributes,
+        );
+    }
+
+    fn check_domain_owner(&mut self, domain: &[u8], owner: &Address) {
+        self.blockchain_wrapper
+            .execute_query(&self.nft_wrapper, |sc| {
+                assert_eq!(
+                    sc.domain_details(&managed_buffer!(domain)).owner,
+                    managed_address!(owner)
+                );
+            })
+            .assert_ok();
+    }
+}
+
+#[test]
+fn register_domains_in_one_collection_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+
+    let now = setup.register(&user, b"alice", 0, START);
+    let now = setup.register(&owner, b"bob", 0, now);
+
+    // Both domains keep their own details and NFT
+    let alice_nonce = setup.nft_nonce(b"alice");
+    let bob_nonce = setup.nft_nonce(b"bob");
+    assert_ne!(alice_nonce, bob_nonce);
+    assert_eq!(
+        setup
+            .blockchain_wrapper
+            .get_esdt_balance(&user, NFT_TOKEN_ID, alice_nonce),
+        rust_biguint!(1u64)
+    );
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert_eq!(
+                sc.domain_details(&managed_buffer!(b"alice")).owner,
+                managed_address!(&user)
+            );
+            assert_eq!(
+                sc.domain_details(&managed_buffer!(b"bob")).owner,
+                managed_address!(&owner)
+            );
+            assert_eq!(
+                sc.nft_domain(&managed_token_id!(NFT_TOKEN_ID), bob_nonce)
+                    .get(),
+                managed_buffer!(b"bob")
+            );
+        })
+        .assert_ok();
+
+    // Approvals are per domain
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.approve(managed_buffer!(b"alice"), managed_address!(&owner));
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert!(sc.token_approval(&managed_buffer!(b"bob")).is_empty());
+        })
+        .assert_ok();
+
+    // Burning an expired domain leaves the other one intact
+    setup
+        .blockchain_wrapper
+        .set_block_timestamp(now + YEAR + GRACE + 1);
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.burn(managed_buffer!(b"alice"));
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert!(!sc.token_details().contains_key(&managed_buffer!(b"alice")));
+            assert!(sc.token_details().contains_key(&managed_buffer!(b"bob")));
+            assert!(sc
+                .nft_domain(&managed_token_id!(NFT_TOKEN_ID), alice_nonce)
+                .is_empty());
+        })
+        .assert_ok();
+}
+
+#[test]
+fn register_only_through_commitment_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+
+    // Minting directly is reserved to the admins
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.create_nft(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"www.xnames.io"),
+                managed_biguint!(0u64),
+                OptionalValue::None,
+                OptionalValue::None,
+                mint_params(b"alice", &user, YEAR),
+            );
+        })
+        .assert_user_error("Unauthorized admin rights by the caller address!");
+
+    // Revealing without a commitment
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.reveal(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"www.xnames.io"),
+                mint_params(b"alice", &user, YEAR),
+                managed_buffer!(b"secret"),
+                OptionalValue::None,
+            );
+        })
+        .assert_user_error("Error: commitment does not exist!");
+
+    // Revealing a commitment too early
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            let hash = sc.get_commitment_hash(
+                managed_buffer!(b"alice"),
+                managed_address!(&user),
+                managed_buffer!(b"secret"),
+            );
+            sc.commit(hash);
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.reveal(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"www.xnames.io"),
+                mint_params(b"alice", &user, YEAR),
+                managed_buffer!(b"secret"),
+                OptionalValue::None,
+            );
+        })
+        .assert_user_error("Error: commitment is too recent!");
+
+    setup.register(&user, b"alice", 0, START + 2 * 86_400);
+    assert!(setup.nft_nonce(b"alice") > 0);
+}
+
+#[test]
+fn outbid_refund_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+    setup
+        .blockchain_wrapper
+        .set_egld_balance(&owner, &rust_biguint!(1_000u64));
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.open_auction(
+                managed_buffer!(b"alice"),
+                managed_token_id!(NFT_TOKEN_ID),
+                YEAR,
+                EgldOrEsdtTokenIdentifier::egld(),
+                managed_biguint!(100u64),
+                START + 86_400,
+            );
+        })
+        .assert_ok();
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(100u64), |sc| {
+            sc.bid(managed_buffer!(b"alice"));
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(200u64), |sc| {
+            sc.bid(managed_buffer!(b"alice"));
+        })
+        .assert_ok();
+
+    // The outbid bid is credited, not pushed back
+    setup
+        .blockchain_wrapper
+        .check_egld_balance(&user, &rust_biguint!(999_999_900u64));
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert_eq!(
+                sc.get_bid_refund(managed_address!(&user), EgldOrEsdtTokenIdentifier::egld()),
+                managed_biguint!(100u64)
+            );
+        })
+        .assert_ok();
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.withdraw_bid(EgldOrEsdtTokenIdentifier::egld());
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .check_egld_balance(&user, &rust_biguint!(1_000_000_000u64));
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.withdraw_bid(EgldOrEsdtTokenIdentifier::egld());
+        })
+        .assert_user_error("Error: no bids to withdraw!");
+
+    // The winning bid stays escrowed until the auction is settled
+    setup
+        .blockchain_wrapper
+        .check_egld_balance(setup.nft_wrapper.address_ref(), &rust_biguint!(200u64));
+}
+
+#[test]
+fn settle_auction_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+    let beneficiary = setup.beneficiary_address.clone();
+    let end = START + 86_400;
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.update_name_restriction(NameRestrictionUpdateParams {
+                restriction: NameRestriction::Blocked,
+                kind: UpdateKind::Add,
+                domain: managed_buffer!(b"evil"),
+            });
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.open_auction(
+                managed_buffer!(b"evil"),
+                managed_token_id!(NFT_TOKEN_ID),
+                YEAR,
+                EgldOrEsdtTokenIdentifier::egld(),
+                managed_biguint!(100u64),
+                end,
+            );
+        })
+        .assert_user_error("Error: domain is blocked!");
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            for domain in [&b"alice"[..], &b"bob"[..]] {
+                sc.open_auction(
+                    managed_buffer!(domain),
+                    managed_token_id!(NFT_TOKEN_ID),
+                    YEAR,
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    managed_biguint!(100u64),
+                    end,
+                );
+            }
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(100u64), |sc| {
+            sc.bid(managed_buffer!(b"alice"));
+        })
+        .assert_ok();
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.settle_auction(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"www.xnames.io"),
+            );
+        })
+        .assert_user_error("Error: auction has not ended!");
+
+    // The highest bidder is minted the domain, and the bid is split in the
+    // treasury
+    setup.blockchain_wrapper.set_block_timestamp(end);
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.settle_auction(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"www.xnames.io"),
+            );
+        })
+        .assert_ok();
+    setup.check_domain_owner(b"alice", &user);
+    let alice_nonce = setup.nft_nonce(b"alice");
+    assert_eq!(
+        setup
+            .blockchain_wrapper
+            .get_esdt_balance(&user, NFT_TOKEN_ID, alice_nonce),
+        rust_biguint!(1u64)
+    );
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert_eq!(
+                sc.domain_details(&managed_buffer!(b"alice")).expiry,
+                end + YEAR
+            );
+            assert!(!sc.auctions().contains_key(&managed_buffer!(b"alice")));
+            assert_eq!(
+                sc.get_fee_balance(
+                    managed_address!(&beneficiary),
+                    EgldOrEsdtTokenIdentifier::egld()
+                ),
+                managed_biguint!(100u64)
+            );
+        })
+        .assert_ok();
+
+    // An auction without bids is closed without minting
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.settle_auction(
+                managed_buffer!(b"bob"),
+                managed_buffer!(b"bob"),
+                managed_buffer!(b"www.xnames.io"),
+            );
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert!(!sc.auctions().contains_key(&managed_buffer!(b"bob")));
+            assert!(sc.find_domain_details(&managed_buffer!(b"bob")).is_none());
+        })
+        .assert_ok();
+}
+
+#[test]
+fn transfer_payments_match_transfers_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+
+    let now = setup.register(&user, b"alice", 0, START);
+    setup.register(&user, b"bob", 0, now);
+    let alice_nonce = setup.nft_nonce(b"alice");
+    let bob_nonce = setup.nft_nonce(b"bob");
+
+    // An NFT that is not transferred would be locked
+    setup
+        .transfer(&user, &[alice_nonce, bob_nonce], b"alice", 1, &user, &owner)
+        .assert_user_error("Error: payments must be the NFTs of the transfers!");
+    setup
+        .transfer(&user, &[alice_nonce], b"alice", 0, &user, &owner)
+        .assert_user_error("Error: payments must be the NFTs of the transfers!");
+    setup
+        .transfer(&user, &[], b"alice", 1, &user, &owner)
+        .assert_user_error("Error: domain NFT must be sent with the transfer!");
+
+    setup
+        .transfer(&user, &[alice_nonce], b"alice", 1, &user, &owner)
+        .assert_ok();
+    setup.check_domain_owner(b"alice", &owner);
+    assert_eq!(
+        setup
+            .blockchain_wrapper
+            .get_esdt_balance(&owner, NFT_TOKEN_ID, alice_nonce),
+        rust_biguint!(1u64)
+    );
+    assert_eq!(
+        setup
+            .blockchain_wrapper
+            .get_esdt_balance(&user, NFT_TOKEN_ID, bob_nonce),
+        rust_biguint!(1u64)
+    );
+}
+
+#[test]
+fn buy_nft_updates_owner_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.nft_token_id().set(managed_token_id!(NFT_TOKEN_ID));
+            sc.create_nft(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"www.xnames.io"),
+                managed_biguint!(500u64),
+                OptionalValue::None,
+                OptionalValue::None,
+                mint_params(b"alice", &owner, YEAR),
+            );
+        })
+        .assert_ok();
+    setup.check_domain_owner(b"alice", &owner);
+
+    let alice_nonce = setup.nft_nonce(b"alice");
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(500u64), |sc| {
+            sc.buy_nft(alice_nonce);
+        })
+        .assert_ok();
+
+    setup.check_domain_owner(b"alice", &user);
+    assert_eq!(
+        setup
+            .blockchain_wrapper
+            .get_esdt_balance(&user, NFT_TOKEN_ID, alice_nonce),
+        rust_biguint!(1u64)
+    );
+}
+
+#[test]
+fn buy_nft_royalty_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+    let beneficiary = setup.beneficiary_address.clone();
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.update_internal_value(UpdateInternalValueParams::Royalty(managed_biguint!(
+                10_001u64
+            )));
+        })
+        .assert_user_error("Error: royalty must not exceed 100%!");
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.nft_token_id().set(managed_token_id!(NFT_TOKEN_ID));
+            sc.create_nft(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"www.xnames.io"),
+                managed_biguint!(10_000u64),
+                OptionalValue::None,
+                OptionalValue::None,
+                mint_params(b"alice", &owner, YEAR),
+            );
+        })
+        .assert_ok();
+
+    // 10% of the sale accrues to the beneficiary, the rest goes to the owner
+    let alice_nonce = setup.nft_nonce(b"alice");
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(10_000u64), |sc| {
+            sc.buy_nft(alice_nonce);
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .check_egld_balance(&owner, &rust_biguint!(9_000u64));
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert_eq!(
+                sc.get_accumulated_royalties(EgldOrEsdtTokenIdentifier::egld(), 0),
+                managed_biguint!(1_000u64)
+            );
+        })
+        .assert_ok();
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.withdraw_royalties(EgldOrEsdtTokenIdentifier::egld(), 0);
+        })
+        .assert_user_error("Error: caller must be the beneficiary!");
+    setup
+        .blockchain_wrapper
+        .execute_tx(
+            &beneficiary,
+            &setup.nft_wrapper,
+            &rust_biguint!(0u64),
+            |sc| {
+                sc.withdraw_royalties(EgldOrEsdtTokenIdentifier::egld(), 0);
+            },
+        )
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .check_egld_balance(&beneficiary, &rust_biguint!(1_000u64));
+    setup
+        .blockchain_wrapper
+        .execute_tx(
+            &beneficiary,
+            &setup.nft_wrapper,
+            &rust_biguint!(0u64),
+            |sc| {
+                sc.withdraw_royalties(EgldOrEsdtTokenIdentifier::egld(), 0);
+            },
+        )
+        .assert_user_error("Error: no royalties to withdraw!");
+}
+
+#[test]
+fn operator_transfer_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+    let operator = setup
+        .blockchain_wrapper
+        .create_user_account(&rust_biguint!(0u64));
+
+    setup.register(&user, b"alice", 0, START);
+    let alice_nonce = setup.nft_nonce(b"alice");
+
+    // An unapproved caller cannot transfer the domain
+    setup
+        .transfer(&owner, &[], b"alice", 1, &user, &owner)
+        .assert_user_error(
+            "Error: caller must be the owner of the domain or an approved operator!",
+        );
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.approve(managed_buffer!(b"alice"), managed_address!(&operator));
+        })
+        .assert_ok();
+
+    // An approved operator must hold the NFT to transfer the domain
+    setup
+        .transfer(&operator, &[], b"alice", 1, &user, &owner)
+        .assert_user_error("Error: domain NFT must be sent with the transfer!");
+
+    // The operator escrows the NFT, e.g. as a marketplace listing
+    setup.move_nft(&user, &operator, alice_nonce);
+    setup
+        .transfer(&operator, &[alice_nonce], b"alice", 1, &user, &owner)
+        .assert_ok();
+    setup.check_domain_owner(b"alice", &owner);
+    assert_eq!(
+        setup
+            .blockchain_wrapper
+            .get_esdt_balance(&owner, NFT_TOKEN_ID, alice_nonce),
+        rust_biguint!(1u64)
+    );
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert!(sc.token_approval(&managed_buffer!(b"alice")).is_empty());
+        })
+        .assert_ok();
+
+    // The seller holds no NFT to claim the domain back with
+    let claim = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        setup.blockchain_wrapper.execute_esdt_transfer(
+            &user,
+            &setup.nft_wrapper,
+            NFT_TOKEN_ID,
+            alice_nonce,
+            &rust_biguint!(1u64),
+            |sc| {
+                sc.claim_ownership();
+            },
+        )
+    }));
+    let error = claim
+        .err()
+        .and_then(|error| error.downcast::<TxPanic>().ok())
+        .expect("claiming without the NFT must fail");
+    assert_eq!(error.message, "insufficient funds");
+    setup.check_domain_owner(b"alice", &owner);
+    setup
+        .transfer(&user, &[], b"alice", 1, &owner, &user)
+        .assert_user_error(
+            "Error: caller must be the owner of the domain or an approved operator!",
+        );
+
+    // The new owner can transfer the domain onwards
+    setup
+        .transfer(&owner, &[alice_nonce], b"alice", 1, &owner, &user)
+        .assert_ok();
+    setup.check_domain_owner(b"alice", &user);
+}
+
+#[test]
+fn approval_for_all_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+    let operator = setup
+        .blockchain_wrapper
+        .create_user_account(&rust_biguint!(0u64));
+
+    let now = setup.register(&user, b"alice", 0, START);
+    setup.register(&user, b"bob", 0, now);
+    let bob_nonce = setup.nft_nonce(b"bob");
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_approval_for_all(managed_address!(&user), true);
+        })
+        .assert_user_error("Error: caller cannot be its own operator!");
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_approval_for_all(managed_address!(&operator), true);
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert!(sc.is_approved_for_all(managed_address!(&user), managed_address!(&operator)));
+            assert!(!sc.is_approved_for_all(managed_address!(&operator), managed_address!(&user)));
+        })
+        .assert_ok();
+
+    // An operator for all domains may approve others for a single domain
+    setup
+        .blockchain_wrapper
+        .execute_tx(&operator, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.approve(managed_buffer!(b"alice"), managed_address!(&owner));
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert_eq!(
+                sc.token_approval(&managed_buffer!(b"alice")).get(),
+                managed_address!(&owner)
+            );
+        })
+        .assert_ok();
+
+    // ... and transfer any domain of the owner, with its NFT
+    setup.move_nft(&user, &operator, bob_nonce);
+    setup
+        .transfer(&operator, &[bob_nonce], b"bob", 1, &user, &owner)
+        .assert_ok();
+    setup.check_domain_owner(b"bob", &owner);
+
+    // Revoking the operator removes its rights
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_approval_for_all(managed_address!(&operator), false);
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert!(!sc.is_approved_for_all(managed_address!(&user), managed_address!(&operator)));
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_tx(&operator, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.approve(managed_buffer!(b"alice"), managed_address!(&operator));
+        })
+        .assert_user_error(
+            "Error: caller must be the owner of the domain or an approved operator!",
+        );
+}
+
+#[test]
+fn claim_ownership_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+    let operator = setup
+        .blockchain_wrapper
+        .create_user_account(&rust_biguint!(0u64));
+
+    let now = setup.register(&user, b"alice", 0, START);
+    let alice_nonce = setup.nft_nonce(b"alice");
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.approve(managed_buffer!(b"alice"), managed_address!(&operator));
+            sc.set_primary_name(managed_buffer!(b"alice"));
+        })
+        .assert_ok();
+
+    // The NFT changes hands outside of the contract, e.g. on a marketplace
+    setup.move_nft(&user, &owner, alice_nonce);
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &owner,
+            &setup.nft_wrapper,
+            NFT_TOKEN_ID,
+            alice_nonce,
+            &rust_biguint!(1u64),
+            |sc| {
+                sc.claim_ownership();
+            },
+        )
+        .assert_ok();
+    setup.check_domain_owner(b"alice", &owner);
+    assert_eq!(
+        setup
+            .blockchain_wrapper
+            .get_esdt_balance(&owner, NFT_TOKEN_ID, alice_nonce),
+        rust_biguint!(1u64)
+    );
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert!(sc.token_approval(&managed_buffer!(b"alice")).is_empty());
+            assert!(sc.primary_name(&managed_address!(&user)).is_empty());
+        })
+        .assert_ok();
+
+    // Only domain NFTs can be claimed
+    setup
+        .blockchain_wrapper
+        .set_esdt_balance(&owner, b"OTHER-123456", &rust_biguint!(1u64));
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &owner,
+            &setup.nft_wrapper,
+            b"OTHER-123456",
+            0,
+            &rust_biguint!(1u64),
+            |sc| {
+                sc.claim_ownership();
+            },
+        )
+        .assert_user_error("Error: payment is not a domain NFT!");
+
+    // The NFT of a burnt domain is no longer bound to it
+    setup
+        .blockchain_wrapper
+        .set_block_timestamp(now + YEAR + GRACE + 1);
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.burn(managed_buffer!(b"alice"));
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &owner,
+            &setup.nft_wrapper,
+            NFT_TOKEN_ID,
+            alice_nonce,
+            &rust_biguint!(1u64),
+            |sc| {
+                sc.claim_ownership();
+            },
+        )
+        .assert_user_error("Error: payment is not a domain NFT!");
+}
+
+#[test]
+fn domain_receiver_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+
+    let nft_address = setup.nft_wrapper.address_ref().clone();
+    let shard = shard_of(&nft_address);
+    let mut receivers = Vec::new();
+    for (tag, receiver_shard, accept) in [
+        (1, shard, true),
+        (2, shard, false),
+        (3, (shard + 1) % 3, true),
+    ] {
+        let receiver = setup.blockchain_wrapper.create_sc_account_fixed_address(
+            &sc_address_in_shard(&nft_address, tag, receiver_shard),
+            &rust_biguint!(0u64),
+            Some(&owner),
+            domain_receiver_mock::contract_obj,
+            DOMAIN_RECEIVER_MOCK_WASM_PATH,
+        );
+        setup
+            .blockchain_wrapper
+            .execute_tx(&owner, &receiver, &rust_biguint!(0u64), |sc| {
+                sc.init(accept);
+            })
+            .assert_ok();
+        receivers.push(receiver);
+    }
+    let accepting = receivers[0].address_ref().clone();
+    let rejecting = receivers[1].address_ref().clone();
+    let other_shard = receivers[2].address_ref().clone();
+
+    setup.register(&user, b"alice", 0, START);
+    let alice_nonce = setup.nft_nonce(b"alice");
+
+    // A rejecting receiver reverts the whole transfer
+    setup
+        .transfer(&user, &[alice_nonce], b"alice", 1, &user, &rejecting)
+        .assert_user_error("Error: receiver rejected the domain!");
+    setup.check_domain_owner(b"alice", &user);
+    assert_eq!(
+        setup
+            .blockchain_wrapper
+            .get_esdt_balance(&user, NFT_TOKEN_ID, alice_nonce),
+        rust_biguint!(1u64)
+    );
+
+    // A receiver in another shard cannot be called synchronously
+    setup
+        .transfer(&user, &[alice_nonce], b"alice", 1, &user, &other_shard)
+        .assert_user_error("Error: receiver must be in the same shard!");
+    setup.check_domain_owner(b"alice", &user);
+
+    // An accepting receiver gets the NFT along with the transfer data
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user,
+            &setup.nft_wrapper,
+            NFT_TOKEN_ID,
+            alice_nonce,
+            &rust_biguint!(1u64),
+            |sc| {
+                sc.transfer(vec![Transfer {
+                    domain: managed_buffer!(b"Alice"),
+                    amount: 1,
+                    from: managed_address!(&user),
+                    to: managed_address!(&accepting),
+                    data: b"listing".to_vec(),
+                }]);
+            },
+        )
+        .assert_ok();
+    setup.check_domain_owner(b"alice", &accepting);
+    assert_eq!(
+        setup
+            .blockchain_wrapper
+            .get_esdt_balance(&accepting, NFT_TOKEN_ID, alice_nonce),
+        rust_biguint!(1u64)
+    );
+    setup
+        .blockchain_wrapper
+        .execute_query(&receivers[0], |sc| {
+            assert_eq!(sc.last_domain().get(), managed_buffer!(b"alice"));
+            assert_eq!(sc.last_from().get(), managed_address!(&user));
+            assert_eq!(sc.last_data().get(), managed_buffer!(b"listing"));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn records_per_domain_cap_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+
+    let now = setup.register(&user, b"alice", 0, START);
+
+    for index in 0..32u32 {
+        setup
+            .blockchain_wrapper
+            .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+                let key = format!("key{}", index);
+                sc.set_text_record(
+                    managed_buffer!(b"alice"),
+                    managed_buffer!(key.as_bytes()),
+                    managed_buffer!(b"value"),
+                );
+                sc.set_coin_address_record(
+                    managed_buffer!(b"alice"),
+                    1_000 + index,
+                    managed_buffer!(b"address"),
+                );
+            })
+            .assert_ok();
+    }
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_text_record(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"key32"),
+                managed_buffer!(b"value"),
+            );
+        })
+        .assert_user_error("Error: too many text records!");
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_coin_address_record(
+                managed_buffer!(b"alice"),
+                1_032,
+                managed_buffer!(b"address"),
+            );
+        })
+        .assert_user_error("Error: too many coin address records!");
+
+    // Existing records can still be updated
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_text_record(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"key0"),
+                managed_buffer!(b"updated"),
+            );
+            sc.set_coin_address_record(
+                managed_buffer!(b"alice"),
+                1_000,
+                managed_buffer!(b"updated"),
+            );
+        })
+        .assert_ok();
+
+    // All records are cleared when the domain is burnt
+    setup
+        .blockchain_wrapper
+        .set_block_timestamp(now + YEAR + GRACE + 1);
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.burn(managed_buffer!(b"alice"));
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert!(sc.text_records(&managed_buffer!(b"alice")).is_empty());
+            assert!(sc
+                .coin_address_records(&managed_buffer!(b"alice"))
+                .is_empty());
+        })
+        .assert_ok();
+}
+
+#[test]
+fn primary_name_follows_address_record_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+
+    setup.register(&user, b"alice", 0, START);
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_address_record(managed_buffer!(b"alice"), managed_address!(&owner));
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_primary_name(managed_buffer!(b"alice"));
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_primary_name(managed_buffer!(b"alice"));
+        })
+        .assert_ok();
+
+    // Pointing the domain elsewhere releases the claim of the old address
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_address_record(managed_buffer!(b"alice"), managed_address!(&user));
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert!(sc.primary_name(&managed_address!(&owner)).is_empty());
+            assert_eq!(
+                sc.primary_name(&managed_address!(&user)).get(),
+                managed_buffer!(b"alice")
+            );
+        })
+        .assert_ok();
+
+    // The owner keeps its claim when the domain stops resolving to it
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_address_record(managed_buffer!(b"alice"), managed_address!(&owner));
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert_eq!(
+                sc.primary_name(&managed_address!(&user)).get(),
+                managed_buffer!(b"alice")
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn expired_domain_does_not_resolve_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+
+    let now = setup.register(&user, b"alice", 0, START);
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_address_record(managed_buffer!(b"alice"), managed_address!(&user));
+            sc.set_coin_address_record(
+                managed_buffer!(b"alice"),
+                1_000,
+                managed_buffer!(b"address"),
+            );
+            sc.set_text_record(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"url"),
+                managed_buffer!(b"xnames.io"),
+            );
+            sc.set_primary_name(managed_buffer!(b"alice"));
+        })
+        .assert_ok();
+
+    // Records still resolve during the grace period
+    setup
+        .blockchain_wrapper
+        .set_block_timestamp(now + YEAR + GRACE);
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert_eq!(
+                sc.resolve(managed_buffer!(b"alice")).into_option(),
+                Some(managed_address!(&user))
+            );
+            assert_eq!(
+                sc.get_coin_address(managed_buffer!(b"alice"), 1_000)
+                    .into_option(),
+                Some(managed_buffer!(b"address"))
+            );
+            assert_eq!(
+                sc.get_primary_name(managed_address!(&user)).into_option(),
+                Some(managed_buffer!(b"alice"))
+            );
+        })
+        .assert_ok();
+
+    setup
+        .blockchain_wrapper
+        .set_block_timestamp(now + YEAR + GRACE + 1);
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert!(sc
+                .resolve(managed_buffer!(b"alice"))
+                .into_option()
+                .is_none());
+            assert!(sc
+                .get_coin_address(managed_buffer!(b"alice"), 1_000)
+                .into_option()
+                .is_none());
+            assert_eq!(sc.get_coin_addresses(managed_buffer!(b"alice")).len(), 0);
+            assert!(sc
+                .get_primary_name(managed_address!(&user))
+                .into_option()
+                .is_none());
+
+            let mut keys = MultiValueEncoded::new();
+            keys.push(managed_buffer!(b"url"));
+            let (key, value) = sc
+                .get_text_records(managed_buffer!(b"alice"), keys)
+                .into_iter()
+                .next()
+                .unwrap()
+                .into_tuple();
+            assert_eq!(key, managed_buffer!(b"url"));
+            assert!(value.is_empty());
+        })
+        .assert_ok();
+}
+
+#[test]
+fn assign_reserved_name_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.update_name_restriction(NameRestrictionUpdateParams {
+                restriction: NameRestriction::Reserved,
+                kind: UpdateKind::Add,
+                domain: managed_buffer!(b"elrond"),
+            });
+        })
+        .assert_ok();
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.assign_reserved_name(
+                managed_buffer!(b"elrond"),
+                managed_buffer!(b"www.xnames.io"),
+                mint_params(b"Elrond", &user, YEAR),
+            );
+        })
+        .assert_ok();
+
+    setup.check_domain_owner(b"elrond", &user);
+    let elrond_nonce = setup.nft_nonce(b"elrond");
+    assert_eq!(
+        setup
+            .blockchain_wrapper
+            .get_esdt_balance(&user, NFT_TOKEN_ID, elrond_nonce),
+        rust_biguint!(1u64)
+    );
+}
+
+#[test]
+fn premium_decay_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+
+    let now = setup.register(&user, b"alice", 0, START);
+    let released_at = now + YEAR + GRACE;
+
+    // No premium while the domain is registered, grace period included
+    for timestamp in [now, now + YEAR, released_at] {
+        setup.blockchain_wrapper.set_block_timestamp(timestamp);
+        setup
+            .blockchain_wrapper
+            .execute_query(&setup.nft_wrapper, |sc| {
+                assert_eq!(
+                    sc.get_premium(managed_buffer!(b"alice")),
+                    managed_biguint!(0)
+                );
+            })
+            .assert_ok();
+    }
+
+    // Halving every day, linearly in between, until the premium duration ends
+    let curve: [(u64, u64); 6] = [
+        (1, 99_999_422),
+        (43_200, 75_000_000),
+        (86_400, 50_000_000),
+        (129_600, 37_500_000),
+        (172_800, 25_000_000),
+        (1_814_400, 0),
+    ];
+    for (elapsed, premium) in curve {
+        setup
+            .blockchain_wrapper
+            .set_block_timestamp(released_at + elapsed);
+        setup
+            .blockchain_wrapper
+            .execute_query(&setup.nft_wrapper, |sc| {
+                assert_eq!(
+                    sc.get_premium(managed_buffer!(b"alice")),
+                    managed_biguint!(premium)
+                );
+            })
+            .assert_ok();
+    }
+}
+
+#[test]
+fn subdomains_per_domain_cap_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+
+    let now = setup.register(&user, b"alice", 0, START);
+
+    for index in 0..32u32 {
+        setup
+            .blockchain_wrapper
+            .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+                let label = format!("sub{}", index);
+                sc.create_subdomain(
+                    managed_buffer!(b"alice"),
+                    managed_buffer!(label.as_bytes()),
+                    managed_address!(&owner),
+                    now + 1_000,
+                );
+            })
+            .assert_ok();
+    }
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.create_subdomain(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"sub32"),
+                managed_address!(&owner),
+                now + 1_000,
+            );
+        })
+        .assert_user_error("Error: too many subdomains!");
+
+    // Expired subdomains can be replaced, and revoked ones free their slot
+    setup.blockchain_wrapper.set_block_timestamp(now + 1_001);
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.create_subdomain(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"sub0"),
+                managed_address!(&user),
+                now + 2_000,
+            );
+            sc.revoke_subdomain(managed_buffer!(b"alice"), managed_buffer!(b"sub1"));
+            sc.create_subdomain(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"sub32"),
+                managed_address!(&owner),
+                now + 2_000,
+            );
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert_eq!(sc.subdomains(&managed_buffer!(b"alice")).len(), 32);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn subdomain_address_record_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+
+    let now = setup.register(&user, b"alice", 0, START);
+    for expiry in [now - 1, now] {
+        setup
+            .blockchain_wrapper
+            .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+                sc.create_subdomain(
+                    managed_buffer!(b"alice"),
+                    managed_buffer!(b"pay"),
+                    managed_address!(&owner),
+                    expiry,
+                );
+            })
+            .assert_user_error("Error: subdomain expiry must be in the future!");
+    }
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.create_subdomain(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"pay"),
+                managed_address!(&owner),
+                now + 1_000,
+            );
+        })
+        .assert_ok();
+
+    // Only the subdomain owner sets its address record
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_subdomain_address_record(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"pay"),
+                managed_address!(&user),
+            );
+        })
+        .assert_user_error("Error: caller must be the owner of the subdomain!");
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_subdomain_address_record(
+                managed_buffer!(b"Alice"),
+                managed_buffer!(b"PAY"),
+                managed_address!(&owner),
+            );
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert_eq!(
+                sc.resolve_subdomain(managed_buffer!(b"alice"), managed_buffer!(b"pay"))
+                    .into_option(),
+                Some(managed_address!(&owner))
+            );
+        })
+        .assert_ok();
+
+    // Reassigning the subdomain clears its address record
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.reassign_subdomain(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"pay"),
+                managed_address!(&user),
+            );
+            assert!(sc
+                .resolve_subdomain(managed_buffer!(b"alice"), managed_buffer!(b"pay"))
+                .into_option()
+                .is_none());
+
+            sc.set_subdomain_address_record(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"pay"),
+                managed_address!(&user),
+            );
+        })
+        .assert_ok();
+
+    // Expired subdomains do not resolve
+    setup.blockchain_wrapper.set_block_timestamp(now + 1_001);
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert!(sc
+                .resolve_subdomain(managed_buffer!(b"alice"), managed_buffer!(b"pay"))
+                .into_option()
+                .is_none());
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_subdomain_address_record(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"pay"),
+                managed_address!(&user),
+            );
+        })
+        .assert_user_error("Error: subdomain is expired!");
+}
+
+#[test]
+fn treasury_fee_split_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+    let beneficiary = setup.beneficiary_address.clone();
+    let treasury = setup
+        .blockchain_wrapper
+        .create_user_account(&rust_biguint!(0u64));
+    let burn = setup
+        .blockchain_wrapper
+        .create_user_account(&rust_biguint!(0u64));
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.update_fee_split(FeeSplitUpdateParams {
+                kind: UpdateKind::Add,
+                recipient: managed_address!(&treasury),
+                share: 3_333,
+            });
+        })
+        .assert_user_error("Unauthorized admin rights by the caller address!");
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.update_fee_split(FeeSplitUpdateParams {
+                kind: UpdateKind::Add,
+                recipient: managed_address!(&treasury),
+                share: 3_333,
+            });
+            sc.update_fee_split(FeeSplitUpdateParams {
+                kind: UpdateKind::Add,
+                recipient: managed_address!(&burn),
+                share: 6_000,
+            });
+        })
+        .assert_ok();
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.update_fee_split(FeeSplitUpdateParams {
+                kind: UpdateKind::Add,
+                recipient: managed_address!(&user),
+                share: 1_000,
+            });
+        })
+        .assert_user_error("Error: fee split shares exceed 100%!");
+
+    // A share wrapping the total around u32 is rejected on its own
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.update_fee_split(FeeSplitUpdateParams {
+                kind: UpdateKind::Add,
+                recipient: managed_address!(&user),
+                share: u32::MAX - 9_332,
+            });
+        })
+        .assert_user_error("Error: fee split share exceeds 100%!");
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.nft_wrapper, |sc| {
+            assert_eq!(sc.get_fee_splits().len(), 2);
+        })
+        .assert_ok();
+
+    // Paying for a registration, then for a renewal whose split rounds down
+    setup.set_price(1);
+    setup
+        .blockchain_wrapper
+        .set_egld_balance(&user, &rust_biguint!(10 * EGLD));
+    let registration_fee = EGLD;
+    let renewal_fee = EGLD + EGLD / YEAR;
+
+    setup.register(&user, b"alice", registration_fee, START);
+    setup
+        .blockchain_wrapper
+        .execute_tx(
+            &user,
+            &setup.nft_wrapper,
+            &rust_biguint!(renewal_fee),
+            |sc| {
+                sc.renew(managed_buffer!(b"alice"), YEAR + 1, OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    // The rounding remainder of every split goes to the beneficiary
+    let split = |share: u128| {
+        registration_fee as u128 * share / 10_000 + renewal_fee as u128 * share / 10_000
+    };
+    let treasury_fees = split(3_333);
+    let burn_fees = split(6_000);
+    let beneficiary_fees = (registration_fee + renewal_fee) as u128 - treasury_fees - burn_fees;
+    assert!(beneficiary_fees > split(667));
+
+    for recipient in [&treasury, &burn, &beneficiary] {
+        setup
+            .blockchain_wrapper
+            .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+                sc.withdraw(
+                    managed_address!(recipient),
+                    EgldOrEsdtTokenIdentifier::egld(),
+                );
+            })
+            .assert_ok();
+    }
+    setup
+        .blockchain_wrapper
+        .check_egld_balance(&treasury, &rust_biguint!(treasury_fees));
+    setup
+        .blockchain_wrapper
+        .check_egld_balance(&burn, &rust_biguint!(burn_fees));
+    setup
+        .blockchain_wrapper
+        .check_egld_balance(&beneficiary, &rust_biguint!(beneficiary_fees));
+    setup
+        .blockchain_wrapper
+        .check_egld_balance(setup.nft_wrapper.address_ref(), &rust_biguint!(0u64));
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.withdraw(
+                managed_address!(&treasury),
+                EgldOrEsdtTokenIdentifier::egld(),
+            );
+        })
+        .assert_user_error("Error: no fees to withdraw!");
+}
+
+#[test]
+fn renew_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+
+    setup.set_price(1);
+    setup
+        .blockchain_wrapper
+        .set_egld_balance(&user, &rust_biguint!(10 * EGLD));
+    let now = setup.register(&user, b"alice", EGLD, START);
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(EGLD - 1), |sc| {
+            sc.renew(managed_buffer!(b"alice"), YEAR, OptionalValue::None);
+        })
+        .assert_user_error("Error: payment is less than the domain price!");
+
+    // Renewing an active subscription extends it from its expiry
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(EGLD), |sc| {
+            sc.renew(managed_buffer!(b"alice"), YEAR, OptionalValue::None);
+            assert_eq!(
+                sc.domain_details(&managed_buffer!(b"alice")).expiry,
+                now + 2 * YEAR
+            );
+        })
+        .assert_ok();
+
+    // Renewing during the grace period extends it from now
+    let now = now + 2 * YEAR + GRACE;
+    setup.blockchain_wrapper.set_block_timestamp(now);
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(EGLD), |sc| {
+            sc.renew(managed_buffer!(b"Alice"), YEAR, OptionalValue::None);
+            assert_eq!(
+                sc.domain_details(&managed_buffer!(b"alice")).expiry,
+                now + YEAR
+            );
+        })
+        .assert_ok();
+
+    setup
+        .blockchain_wrapper
+        .set_block_timestamp(now + YEAR + GRACE + 1);
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(EGLD), |sc| {
+            sc.renew(managed_buffer!(b"alice"), YEAR, OptionalValue::None);
+        })
+        .assert_user_error("Error: subscription has expired!");
+}
+
+#[test]
+fn zero_duration_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+
+    setup.set_price(1);
+    setup
+        .blockchain_wrapper
+        .set_egld_balance(&user, &rust_biguint!(10 * EGLD));
+    let now = setup.register(&user, b"alice", EGLD, START);
+
+    // A zero duration would be priced at zero
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            let hash = sc.get_commitment_hash(
+                managed_buffer!(b"bob"),
+                managed_address!(&user),
+                managed_buffer!(b"secret"),
+            );
+            sc.commit(hash);
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .set_block_timestamp(now + MIN_COMMITMENT_AGE);
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.reveal(
+                managed_buffer!(b"bob"),
+                managed_buffer!(b"www.xnames.io"),
+                mint_params(b"bob", &user, 0),
+                managed_buffer!(b"secret"),
+                OptionalValue::None,
+            );
+        })
+        .assert_user_error("Error: duration must not be zero!");
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.renew(managed_buffer!(b"alice"), 0, OptionalValue::None);
+        })
+        .assert_user_error("Error: duration must not be zero!");
+
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.oracle_wrapper, |sc| {
+            assert_eq!(
+                sc.get_price_for_duration(
+                    managed_buffer!(b"bob"),
+                    YEAR,
+                    EgldOrEsdtTokenIdentifier::egld()
+                ),
+                managed_biguint!(EGLD)
+            );
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.oracle_wrapper, |sc| {
+            sc.get_price_for_duration(
+                managed_buffer!(b"bob"),
+                0,
+                EgldOrEsdtTokenIdentifier::egld(),
+            );
+        })
+        .assert_user_error("Duration must not be zero!");
+}
+
+#[test]
+fn coin_address_records_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+
+    setup.register(&user, b"alice", 0, START);
+
+    let mut p2wpkh = vec![0x00, 0x14];
+    p2wpkh.extend_from_slice(&[0xab; 20]);
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_coin_address_record(managed_buffer!(b"alice"), 60, managed_buffer!(&[0xab; 20]));
+        })
+        .assert_user_error("Error: caller must be the owner of the domain!");
+
+    // Addresses of well-known coin types are validated
+    let rejected: [(u32, &[u8], &str); 5] = [
+        (
+            0,
+            &p2wpkh[..21],
+            "Error: invalid BTC address, expected a P2PKH, P2SH or segwit output script!",
+        ),
+        (
+            60,
+            &[0xab; 19],
+            "Error: invalid ETH address, expected 20 bytes!",
+        ),
+        (
+            508,
+            &[0xab; 31],
+            "Error: invalid EGLD address, expected 32 bytes!",
+        ),
+        (9_999, b"", "Error: address must not be empty!"),
+        (
+            9_999,
+            &[0xab; 129],
+            "Error: address is longer than 128 bytes!",
+        ),
+    ];
+    for (coin_type, address, message) in rejected {
+        setup
+            .blockchain_wrapper
+            .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+                sc.set_coin_address_record(
+                    managed_buffer!(b"alice"),
+                    coin_type,
+                    managed_buffer!(address),
+                );
+            })
+            .assert_user_error(message);
+    }
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_coin_address_record(managed_buffer!(b"alice"), 0, managed_buffer!(&p2wpkh));
+            sc.set_coin_address_record(managed_buffer!(b"alice"), 60, managed_buffer!(&[0xab; 20]));
+            sc.set_coin_address_record(
+                managed_buffer!(b"alice"),
+                508,
+                managed_buffer!(user.as_bytes()),
+            );
+            sc.set_coin_address_record(managed_buffer!(b"alice"), 9_999, managed_buffer!(b"any"));
+            assert_eq!(sc.get_coin_addresses(managed_buffer!(b"alice")).len(), 4);
+
+            sc.remove_coin_address_record(managed_buffer!(b"alice"), 60);
+            assert!(sc
+                .get_coin_address(managed_buffer!(b"alice"), 60)
+                .into_option()
+                .is_none());
+            assert_eq!(
+                sc.get_coin_address(managed_buffer!(b"alice"), 508)
+                    .into_option(),
+                Some(managed_buffer!(user.as_bytes()))
+            );
+        })
+        .assert_ok();
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.remove_coin_address_record(managed_buffer!(b"alice"), 60);
+        })
+        .assert_user_error("Error: coin address record does not exist!");
+}
+
+#[test]
+fn referral_commission_test() {
+    let mut setup = setup_contract(xn_nft::contract_obj, price_oracle::contract_obj);
+    let user = setup.user_address.clone();
+    let owner = setup.owner_address.clone();
+    let referrer = setup
+        .blockchain_wrapper
+        .create_user_account(&rust_biguint!(0u64));
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.update_referrer(ReferrerUpdateParams {
+                kind: UpdateKind::Add,
+                referrer: managed_address!(&referrer),
+                rate: 1_000,
+            });
+        })
+        .assert_ok();
+
+    setup.set_price(1);
+    setup
+        .blockchain_wrapper
+        .set_egld_balance(&user, &rust_biguint!(10 * EGLD));
+
+    // Registering with a referrer
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            let hash = sc.get_commitment_hash(
+                managed_buffer!(b"alice"),
+                managed_address!(&user),
+                managed_buffer!(b"secret"),
+            );
+            sc.commit(hash);
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .set_block_timestamp(START + MIN_COMMITMENT_AGE);
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(EGLD), |sc| {
+            sc.reveal(
+                managed_buffer!(b"alice"),
+                managed_buffer!(b"www.xnames.io"),
+                mint_params(b"alice", &user, YEAR),
+                managed_buffer!(b"secret"),
+                OptionalValue::Some(managed_address!(&referrer)),
+            );
+            assert_eq!(
+                sc.get_referral_balance(
+                    managed_address!(&referrer),
+                    EgldOrEsdtTokenIdentifier::egld()
+                ),
+                managed_biguint!(EGLD / 10)
+            );
+        })
+        .assert_ok();
+
+    // Renewing with a referrer, which cannot be the payer
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(EGLD), |sc| {
+            sc.renew(
+                managed_buffer!(b"alice"),
+                YEAR,
+                OptionalValue::Some(managed_address!(&user)),
+            );
+        })
+        .assert_user_error("Error: cannot refer yourself!");
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user, &setup.nft_wrapper, &rust_biguint!(EGLD), |sc| {
+            sc.renew(
+                managed_buffer!(b"alice"),
+                YEAR,
+                OptionalValue::Some(managed_address!(&referrer)),
+            );
+            assert_eq!(
+                sc.get_referral_balance(
+                    managed_address!(&referrer),
+                    EgldOrEsdtTokenIdentifier::egld()
+                ),
+                managed_biguint!(2 * EGLD / 10)
+            );
+            assert_eq!(
+                sc.get_fee_balance(
+                    sc.state().get().benificiary,
+                    EgldOrEsdtTokenIdentifier::egld()
+                ),
+                managed_biguint!(2 * EGLD - 2 * EGLD / 10)
+            );
+        })
+        .assert_ok();
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&referrer, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim_referral_rewards(EgldOrEsdtTokenIdentifier::egld());
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .check_egld_balance(&referrer, &rust_biguint!(2 * EGLD / 10));
+    setup
+        .blockchain_wrapper
+        .execute_tx(&referrer, &setup.nft_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim_referral_rewards(EgldOrEsdtTokenIdentifier::egld());
+        })
+        .assert_user_error("Error: no referral rewards to claim!");
+}
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           15
+// Endpoints:                           67
 // Async Callback:                       1
-// Total number of exported functions:  17
+// Total number of exported functions:  69
 
 #![no_std]
 
@@ -15,20 +15,72 @@ elrond_wasm_node::wasm_endpoints! {
     xn_nft
     (
         createNft
+        reveal
+        assignReservedName
+        settleAuction
+        renew
         burn
         transfer
+        claimOwnership
         claimRoyaltiesFromMarketplace
         updateInternalValue
-        state
-        token_details
         getTokenSubscriptionStatus
+        getRegistrationPrice
+        getRenewalPrice
+        isAvailable
+        getAvailability
+        getTokenInfo
         issueToken
         setLocalRoles
         buyNft
+        withdrawRoyalties
         getNftPrice
+        getAccumulatedRoyalties
         updateAuthority
         admins
         maintainers
+        state
+        token_details
+        getNormalizedDomain
+        setAddressRecord
+        resolve
+        setCoinAddressRecord
+        removeCoinAddressRecord
+        getCoinAddress
+        getCoinAddresses
+        setTextRecord
+        removeTextRecord
+        getTextRecords
+        setPrimaryName
+        getPrimaryName
+        createSubdomain
+        reassignSubdomain
+        revokeSubdomain
+        setSubdomainAddressRecord
+        resolveSubdomain
+        getSubdomains
+        commit
+        getCommitmentHash
+        getPremium
+        openAuction
+        bid
+        withdrawBid
+        getBidRefund
+        getAuction
+        updateNameRestriction
+        getNameRestriction
+        approve
+        setApprovalForAll
+        getApproved
+        isApprovedForAll
+        updateFeeSplit
+        withdraw
+        getFeeSplits
+        getFeeBalance
+        updateReferrer
+        claimReferralRewards
+        getReferralRate
+        getReferralBalance
         callBack
     )
 }